@@ -0,0 +1,40 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use rsysfetch::system_info::{Collector, SystemInfo, probes};
+
+fn bench_collect(c: &mut Criterion) {
+    // Reads CPU and memory only; the process table is left for `refresh_dynamic`
+    c.bench_function("collector_new", |b| b.iter(Collector::new));
+
+    let mut collector = Collector::new();
+
+    c.bench_function("collect_static", |b| {
+        b.iter(|| SystemInfo::collect_static(&collector))
+    });
+
+    let mut info = SystemInfo::collect_static(&collector);
+    c.bench_function("refresh_dynamic", |b| {
+        b.iter(|| info.refresh_dynamic(&mut collector))
+    });
+
+    collector.set_include_processes(false);
+    c.bench_function("refresh_dynamic_without_processes", |b| {
+        b.iter(|| info.refresh_dynamic(&mut collector))
+    });
+}
+
+fn bench_probes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("probes");
+    group.bench_function("kernel_build", |b| b.iter(probes::kernel_build));
+    group.bench_function("virtualization", |b| b.iter(probes::virtualization));
+    group.bench_function("gpus", |b| b.iter(probes::gpus));
+    group.bench_function("displays", |b| b.iter(probes::displays));
+    group.bench_function("audio_device", |b| b.iter(probes::audio_device));
+    group.bench_function("power_profile", |b| b.iter(probes::power_profile));
+    group.bench_function("local_ip", |b| b.iter(probes::local_ip));
+    group.bench_function("shell", |b| b.iter(probes::shell));
+    group.bench_function("terminal", |b| b.iter(probes::terminal));
+    group.finish();
+}
+
+criterion_group!(benches, bench_collect, bench_probes);
+criterion_main!(benches);
@@ -1,19 +1,446 @@
 use crate::AppResult;
-use crate::system_info::SystemInfo;
+use crate::config::{
+    self, BarStyle, ByteUnit, Config, DEFAULT_LOGO_WIDTH, LayoutMode, LogoAlign, TempUnit,
+    UnknownToken, UptimeFormat,
+};
+use crate::glyphs::{self, Glyphs};
+use crate::health::{self, Thresholds};
+use crate::history::{CoreHistory, History};
+use crate::logo;
+use crate::output;
+use crate::panel::{InfoItem, Panel};
+use crate::print;
+use crate::record::UptimeRecord;
+use crate::system_info::{Collector, ProcessSort, SystemInfo};
+use crate::theme::{self, Theme};
+use image::DynamicImage;
+use ratatui::style::Color;
+use ratatui::text::Line;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::iter;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default time between refreshes
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How much `+` and `-` change the refresh interval by
+const INTERVAL_STEP: Duration = Duration::from_millis(250);
+
+/// Refreshes taking longer than this are flagged in the status bar
+pub const SLOW_REFRESH: Duration = Duration::from_millis(500);
+
+/// Upper bound on stored samples; the UI shows as many as fit
+const HISTORY_CAPACITY: usize = 256;
 
 #[derive(Debug)]
 pub struct App {
     pub system_info: SystemInfo,
+    collector: Collector,
+    pub theme: Theme,
+    /// Preset the theme started from, advanced by `t`
+    pub theme_preset: String,
+    /// Per-line colors from `[colors]`, taking precedence over the theme
+    pub item_colors: HashMap<InfoItem, Color>,
+    pub thresholds: Thresholds,
+    pub primary_disk: String,
+    pub bar_style: BarStyle,
+    pub temp_unit: TempUnit,
+    pub byte_unit: ByteUnit,
+    pub uptime_format: UptimeFormat,
+    /// Symbols to draw with: Unicode, or plain ASCII (`--ascii`)
+    pub glyphs: &'static Glyphs,
+    /// Draw without colors (`--no-color`, `NO_COLOR`)
+    pub no_color: bool,
+    /// Order of the Processes panel, switched with `p`
+    pub process_sort: ProcessSort,
+    pub logo_width: u16,
+    pub logo_align: LogoAlign,
+    /// Styled lines of the `logo` file
+    pub logo_text: Option<Vec<Line<'static>>>,
+    pub logo_image: Option<DynamicImage>,
+    pub logo_image_color: bool,
     pub should_quit: bool,
+    pub show_details: bool,
+    /// The key binding popup is open (`?`)
+    pub show_help: bool,
+    pub focused: Panel,
+    pub collapsed: HashSet<Panel>,
+    /// Rows scrolled past in the compact layout
+    pub scroll_offset: u16,
+    /// Hardware panels left out of the full layout
+    pub hidden: HashSet<Panel>,
+    /// Hardware panels in display order (`sections`)
+    pub sections: Vec<Panel>,
+    pub status: Option<String>,
+    pub refresh_on_keypress_only: bool,
+    /// Auto-refresh is suspended until toggled again; `r` still refreshes
+    pub paused: bool,
+    pub interval: Duration,
+    pub title: Option<String>,
+    pub title_unknown: UnknownToken,
+    pub layout: LayoutMode,
+    /// Total CPU usage in percent, one sample per refresh
+    pub cpu_history: History,
+    /// Memory used in percent, one sample per refresh
+    pub memory_history: History,
+    pub rx_history: History,
+    pub tx_history: History,
+    pub core_history: CoreHistory,
+    /// Show the public IP line (`--public-ip`)
+    pub show_public_ip: bool,
+    /// Result of the lookup running in the background, until it arrives
+    public_ip_lookup: Option<Receiver<Result<String, String>>>,
+    pub uptime_record: UptimeRecord,
+    /// Refreshes since startup
+    pub refresh_count: u64,
+    /// When the data was last sampled, and how long sampling took
+    pub last_refresh: Instant,
+    pub last_refresh_took: Duration,
+    /// `r` was just pressed; the help bar says so until the next frame
+    pub refreshed_manually: bool,
+    /// Showing given data; refreshing leaves it untouched
+    fixed: bool,
 }
 
 impl App {
-    pub fn new() -> AppResult<Self> {
-        let system_info = SystemInfo::collect()?;
+    pub fn new(config: &Config) -> AppResult<Self> {
+        let mut collector = Collector::new();
+        collector.set_include_loopback(config.all_interfaces.unwrap_or(false));
+        collector.set_include_processes(
+            !config
+                .hidden
+                .iter()
+                .flatten()
+                .any(|panel| *panel == Panel::Processes),
+        );
+        let mut system_info = SystemInfo::collect_static(&collector);
+        system_info.refresh_dynamic(&mut collector);
+
+        let mut uptime_record = UptimeRecord::load();
+        uptime_record.observe(system_info.uptime_seconds);
+
+        let mut app = Self::build(config, system_info, collector, uptime_record, false)?;
+        if app.show_public_ip {
+            // The request can take seconds; don't hold up the first frame
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || sender.send(SystemInfo::lookup_public_ip()));
+            app.public_ip_lookup = Some(receiver);
+        }
+        Ok(app)
+    }
+
+    /// Show the given info instead of sampling the live system (`--mock`, `--print`)
+    pub fn with_info(config: &Config, system_info: SystemInfo) -> AppResult<Self> {
+        let mut uptime_record = UptimeRecord::in_memory();
+        uptime_record.observe(system_info.uptime_seconds);
+
+        let mut app = Self::build(config, system_info, Collector::new(), uptime_record, true)?;
+        // A single column, since the info never changes
+        app.core_history.push(app.system_info.core_usage.clone());
+        app.record_usage();
+        Ok(app)
+    }
+
+    fn build(
+        config: &Config,
+        system_info: SystemInfo,
+        collector: Collector,
+        uptime_record: UptimeRecord,
+        fixed: bool,
+    ) -> AppResult<Self> {
+        let interval = match &config.interval {
+            Some(interval) => config::parse_interval(interval)?,
+            None => DEFAULT_INTERVAL,
+        };
+
+        // An unreadable file or image falls back to the built-in logo
+        let mut status = None;
+        let logo_text = config
+            .logo
+            .as_ref()
+            .and_then(|path| match fs::read_to_string(path) {
+                Ok(text) => Some(logo::parse_ansi(&text)),
+                Err(err) => {
+                    status = Some(format!("Could not load {}: {err}", path.display()));
+                    None
+                }
+            });
+        let logo_image = config
+            .logo_image
+            .as_ref()
+            .and_then(|path| match image::open(path) {
+                Ok(image) => Some(image),
+                Err(err) => {
+                    status = Some(format!("Could not load {}: {err}", path.display()));
+                    None
+                }
+            });
+
+        // Bad values are reported once here and otherwise ignored
+        let (theme, mut invalid) = Theme::from_config(&config.theme);
+        let (item_colors, invalid_items) = theme::item_colors(&config.colors);
+        invalid.extend(invalid_items);
+        let (sections, invalid_sections) =
+            Panel::hardware_order(config.sections.as_deref().unwrap_or_default());
+        invalid.extend(invalid_sections);
+        if !invalid.is_empty() {
+            status = Some(format!("Ignoring {}", invalid.join(", ")));
+        }
 
         Ok(Self {
             system_info,
+            collector,
+            theme,
+            theme_preset: config
+                .theme
+                .preset
+                .clone()
+                .unwrap_or_else(|| "default".to_string()),
+            item_colors,
+            thresholds: Thresholds::from_config(&config.thresholds),
+            primary_disk: config
+                .primary_disk
+                .clone()
+                .unwrap_or_else(|| config::default_primary_disk().to_string()),
+            bar_style: config.bar_style.unwrap_or_default(),
+            temp_unit: config.temp_unit.unwrap_or_default(),
+            byte_unit: config.byte_unit.unwrap_or_default(),
+            uptime_format: config.uptime_format.unwrap_or_default(),
+            glyphs: Glyphs::get(config.ascii.unwrap_or_else(glyphs::locale_lacks_utf8)),
+            no_color: config.no_color.unwrap_or(false) || theme::no_color_env(),
+            process_sort: ProcessSort::default(),
+            // Keep both the logo and the info panel visible
+            logo_width: config
+                .logo_width
+                .unwrap_or(DEFAULT_LOGO_WIDTH)
+                .clamp(10, 90),
+            logo_align: config.logo_align.unwrap_or_default(),
+            logo_text,
+            logo_image,
+            logo_image_color: config.logo_image_color.unwrap_or(false),
             should_quit: false,
+            show_details: false,
+            show_help: false,
+            focused: Panel::System,
+            collapsed: config.collapsed.iter().flatten().copied().collect(),
+            scroll_offset: 0,
+            hidden: config.hidden.iter().flatten().copied().collect(),
+            sections,
+            status,
+            refresh_on_keypress_only: config.refresh_on_keypress_only.unwrap_or(false),
+            paused: false,
+            interval,
+            title: config.title.clone(),
+            title_unknown: config.title_unknown.unwrap_or_default(),
+            layout: config.layout.unwrap_or_default(),
+            cpu_history: History::new(HISTORY_CAPACITY),
+            memory_history: History::new(HISTORY_CAPACITY),
+            rx_history: History::new(HISTORY_CAPACITY),
+            tx_history: History::new(HISTORY_CAPACITY),
+            core_history: CoreHistory::new(HISTORY_CAPACITY),
+            show_public_ip: config.public_ip.unwrap_or(false),
+            public_ip_lookup: None,
+            uptime_record,
+            refresh_count: 0,
+            last_refresh: Instant::now(),
+            last_refresh_took: Duration::ZERO,
+            refreshed_manually: false,
+            fixed,
         })
     }
+
+    /// Re-sample the dynamic system values
+    pub fn refresh(&mut self) {
+        // Given data never changes; only restart the interval
+        if self.fixed {
+            self.last_refresh = Instant::now();
+            return;
+        }
+        let started = Instant::now();
+
+        let previous: Vec<String> = self
+            .system_info
+            .interfaces
+            .iter()
+            .map(|interface| interface.name.clone())
+            .collect();
+
+        self.system_info.refresh_dynamic(&mut self.collector);
+        self.record_throughput(&previous);
+        self.core_history.push(self.system_info.core_usage.clone());
+        self.record_usage();
+        self.uptime_record.observe(self.system_info.uptime_seconds);
+        self.receive_public_ip();
+        // A battery can go away (e.g. pulled from the dock) while its panel is focused
+        if !self.panel_shown(self.focused) {
+            self.focus_next();
+        }
+
+        self.refresh_count += 1;
+        self.last_refresh = Instant::now();
+        self.last_refresh_took = self.last_refresh - started;
+    }
+
+    /// Refresh right away on request, flagging it for the help bar
+    pub fn refresh_now(&mut self) {
+        self.refresh();
+        self.refreshed_manually = true;
+    }
+
+    /// Take the public IP once the background lookup has finished
+    fn receive_public_ip(&mut self) {
+        let Some(receiver) = &self.public_ip_lookup else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(lookup) => {
+                self.system_info.set_public_ip(lookup);
+                self.public_ip_lookup = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.public_ip_lookup = None,
+        }
+    }
+
+    /// Whether the public IP is still being looked up
+    pub fn public_ip_pending(&self) -> bool {
+        self.public_ip_lookup.is_some()
+    }
+
+    /// Append the CPU and memory usage to their histories
+    fn record_usage(&mut self) {
+        let info = &self.system_info;
+        self.cpu_history.push(info.cpu_usage.round() as u64);
+        self.memory_history
+            .push(health::percent(info.memory_used, info.memory_total).round() as u64);
+    }
+
+    /// Append the total throughput to the history, starting over when an interface went away
+    fn record_throughput(&mut self, previous: &[String]) {
+        let interfaces = &self.system_info.interfaces;
+
+        let vanished = previous
+            .iter()
+            .any(|name| !interfaces.iter().any(|interface| &interface.name == name));
+        if vanished {
+            self.rx_history.clear();
+            self.tx_history.clear();
+        }
+
+        self.rx_history
+            .push(interfaces.iter().map(|interface| interface.rx_rate).sum());
+        self.tx_history
+            .push(interfaces.iter().map(|interface| interface.tx_rate).sum());
+    }
+
+    /// Refresh less (`slower`) or more often, one `INTERVAL_STEP` at a time down to `MIN_INTERVAL`
+    pub fn adjust_interval(&mut self, slower: bool) {
+        self.interval = if slower {
+            self.interval + INTERVAL_STEP
+        } else {
+            self.interval
+                .saturating_sub(INTERVAL_STEP)
+                .max(config::MIN_INTERVAL)
+        };
+    }
+
+    /// Switch to the next built-in theme, dropping any per-role overrides
+    pub fn cycle_theme(&mut self) {
+        let name = Theme::next_preset(&self.theme_preset);
+        self.theme = Theme::preset(name).unwrap_or_default();
+        self.theme_preset = name.to_string();
+        self.status = Some(format!("Theme: {name}"));
+    }
+
+    /// Sort the process list by the other column; the next refresh picks the new top ones
+    pub fn toggle_process_sort(&mut self) {
+        self.process_sort = self.process_sort.toggled();
+        self.collector.set_process_sort(self.process_sort);
+        self.process_sort.sort(&mut self.system_info.top_processes);
+    }
+
+    /// Whether the panel is drawn; the battery panel only shows on machines with a battery
+    pub fn panel_shown(&self, panel: Panel) -> bool {
+        !self.hidden.contains(&panel)
+            && (panel != Panel::Battery || self.system_info.battery.is_some())
+    }
+
+    /// Show or hide the `index`th hardware panel (from 0) and remember the choice in the config
+    pub fn toggle_hidden(&mut self, index: usize) {
+        let Some(panel) = Panel::HARDWARE.get(index).copied() else {
+            return;
+        };
+        if !self.hidden.remove(&panel) {
+            self.hidden.insert(panel);
+        }
+        if panel == Panel::Processes {
+            self.collector
+                .set_include_processes(!self.hidden.contains(&Panel::Processes));
+        }
+        if !self.panel_shown(self.focused) {
+            self.focus_next();
+        }
+
+        self.status = config::save_hidden(&self.hidden)
+            .err()
+            .map(|err| format!("Could not save config: {err}"));
+    }
+
+    /// Move the focus to the next shown panel
+    pub fn focus_next(&mut self) {
+        self.step_focus(1);
+    }
+
+    /// Move the focus to the previous shown panel
+    pub fn focus_previous(&mut self) {
+        self.step_focus(-1);
+    }
+
+    /// Move the focus `step` panels along the display order, wrapping around
+    fn step_focus(&mut self, step: isize) {
+        let order: Vec<Panel> = iter::once(Panel::System)
+            .chain(self.sections.iter().copied())
+            .collect();
+        let len = order.len() as isize;
+        let mut index = order
+            .iter()
+            .position(|panel| *panel == self.focused)
+            .unwrap_or(0) as isize;
+        loop {
+            index = (index + step).rem_euclid(len);
+            if self.panel_shown(order[index as usize]) {
+                break;
+            }
+        }
+        self.focused = order[index as usize];
+    }
+
+    /// Scroll the compact layout by `delta` rows, staying within `0..=max`
+    pub fn scroll_by(&mut self, delta: i32, max: u16) {
+        let offset = i32::from(self.scroll_offset) + delta;
+        self.scroll_offset = offset.clamp(0, i32::from(max)) as u16;
+    }
+
+    /// Write the plain text report to a timestamped file in the current directory
+    pub fn save_report(&mut self) {
+        let path = output::report_path();
+        self.status = Some(match output::write(&path, &print::render(self, false)) {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(err) => format!("Could not save report: {err}"),
+        });
+    }
+
+    /// Collapse or expand the focused panel and remember the choice in the config
+    pub fn toggle_collapsed(&mut self) {
+        if !self.collapsed.remove(&self.focused) {
+            self.collapsed.insert(self.focused);
+        }
+
+        self.status = config::save_collapsed(&self.collapsed)
+            .err()
+            .map(|err| format!("Could not save config: {err}"));
+    }
 }
@@ -0,0 +1,209 @@
+use crate::AppResult;
+use crate::config::{self, ByteUnit, Config, LayoutMode, TempUnit};
+use crate::fields;
+use crate::output::OutputFormat;
+use std::env;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: rsysfetch [OPTIONS]
+
+Options:
+      --refresh-on-keypress-only  Disable auto-refresh; only 'r' refreshes the stats
+      --interval <DURATION>       Time between refreshes, e.g. 500ms, 2s or 1m (default: 1s)
+      --layout <MODE>             Layout to use: compact, full or auto (default: auto)
+      --primary-disk <MOUNT>      Disk shown in the compact layout (default: / or C:\\)
+      --temp-unit <UNIT>          Show temperatures in c or f (default: c)
+      --si                        Show sizes in decimal units (KB = 1000 bytes) instead of KiB
+      --ascii                     Draw with plain ASCII instead of emoji and box drawing
+      --no-color                  Draw without colors, as when NO_COLOR is set
+      --theme <NAME>              Color theme: default, retro, mono, dracula, gruvbox or solarized
+      --logo <PATH>               Show an ASCII or ANSI art file as the logo
+      --logo-image <PATH>         Render a PNG or JPEG image as the logo
+      --public-ip                 Look up the public IP address (sends a request to api.ipify.org)
+      --all-interfaces            List loopback interfaces in the Network panel too
+      --inline                    Draw in the normal screen instead of the alternate screen
+      --format <FORMAT>           Print the system information as yaml, json or env and exit
+      --json                      Same as --format json
+      --fields <LIST>             Comma-separated fields to include with --format
+      --list-fields               Print every available field and exit
+      --oneline                   Print a one-line summary for shell prompts and exit
+  -p, --print                     Print the logo and info as plain text and exit (default when piped)
+  -o, --output <PATH>             Write the plain text (or --format) output to a file instead and exit
+      --profile <NAME>            Apply the [profiles.<NAME>] section of the config file
+      --check-config              Print the resolved settings and where each comes from, then exit
+      --init-config               Write a commented default config file and exit
+      --force                     Let --init-config overwrite an existing file
+  -h, --help                      Print help
+  -V, --version                   Print version";
+
+/// Command line options
+#[derive(Debug, Clone, Default)]
+pub struct Cli {
+    pub help: bool,
+    pub version: bool,
+    pub check_config: bool,
+    pub profile: Option<String>,
+    pub init_config: bool,
+    pub force: bool,
+    pub refresh_on_keypress_only: bool,
+    pub interval: Option<String>,
+    pub layout: Option<LayoutMode>,
+    pub primary_disk: Option<String>,
+    pub temp_unit: Option<TempUnit>,
+    pub si: bool,
+    pub ascii: bool,
+    pub no_color: bool,
+    pub theme: Option<String>,
+    pub logo: Option<PathBuf>,
+    pub logo_image: Option<PathBuf>,
+    pub public_ip: bool,
+    pub all_interfaces: bool,
+    pub inline: bool,
+    pub format: Option<OutputFormat>,
+    pub fields: Option<Vec<String>>,
+    pub list_fields: bool,
+    pub oneline: bool,
+    pub print: bool,
+    pub output: Option<PathBuf>,
+    /// Hidden: load `SystemInfo` from a JSON fixture instead of the live system
+    pub mock: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Parse the process arguments
+    pub fn parse() -> AppResult<Self> {
+        Self::parse_from(env::args().skip(1))
+    }
+
+    /// Parse the given arguments (without the program name)
+    pub fn parse_from<I: IntoIterator<Item = String>>(args: I) -> AppResult<Self> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // Accept both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for '{flag}'\n\n{USAGE}"))
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => cli.help = true,
+                "-V" | "--version" => cli.version = true,
+                "--check-config" => cli.check_config = true,
+                "--profile" => cli.profile = Some(value()?),
+                "--init-config" => cli.init_config = true,
+                "--force" => cli.force = true,
+                "--refresh-on-keypress-only" => cli.refresh_on_keypress_only = true,
+                "--interval" => {
+                    let interval = value()?;
+                    config::parse_interval(&interval)?;
+                    cli.interval = Some(interval);
+                }
+                "--layout" => cli.layout = Some(value()?.parse()?),
+                "--primary-disk" => cli.primary_disk = Some(value()?),
+                "--temp-unit" => cli.temp_unit = Some(value()?.parse()?),
+                "--si" => cli.si = true,
+                "--ascii" => cli.ascii = true,
+                "--no-color" => cli.no_color = true,
+                "--theme" => {
+                    let name = value()?;
+                    if !config::THEME_PRESETS.contains(&name.as_str()) {
+                        return Err(format!(
+                            "unknown theme '{name}' (expected {})",
+                            config::THEME_PRESETS.join(", ")
+                        )
+                        .into());
+                    }
+                    cli.theme = Some(name);
+                }
+                "--logo" => cli.logo = Some(PathBuf::from(value()?)),
+                "--logo-image" => cli.logo_image = Some(PathBuf::from(value()?)),
+                "--public-ip" => cli.public_ip = true,
+                "--all-interfaces" => cli.all_interfaces = true,
+                "--inline" => cli.inline = true,
+                "--format" => cli.format = Some(value()?.parse()?),
+                "--json" => cli.format = Some(OutputFormat::Json),
+                "--fields" => cli.fields = Some(parse_fields(&value()?)?),
+                "--list-fields" => cli.list_fields = true,
+                "--oneline" => cli.oneline = true,
+                "-p" | "--print" => cli.print = true,
+                "-o" | "--output" => cli.output = Some(PathBuf::from(value()?)),
+                "--mock" => cli.mock = Some(PathBuf::from(value()?)),
+                other => {
+                    return Err(format!("unknown argument '{other}'\n\n{USAGE}").into());
+                }
+            }
+        }
+
+        Ok(cli)
+    }
+
+    /// Apply the settings given on the command line on top of the config file
+    pub fn apply_to(&self, config: &mut Config) {
+        if self.refresh_on_keypress_only {
+            config.refresh_on_keypress_only = Some(true);
+        }
+        if self.interval.is_some() {
+            config.interval = self.interval.clone();
+        }
+        if self.layout.is_some() {
+            config.layout = self.layout;
+        }
+        if self.primary_disk.is_some() {
+            config.primary_disk = self.primary_disk.clone();
+        }
+        if self.temp_unit.is_some() {
+            config.temp_unit = self.temp_unit;
+        }
+        if self.si {
+            config.byte_unit = Some(ByteUnit::Decimal);
+        }
+        if self.ascii {
+            config.ascii = Some(true);
+        }
+        if self.no_color {
+            config.no_color = Some(true);
+        }
+        if self.theme.is_some() {
+            config.theme.preset = self.theme.clone();
+        }
+        if self.logo.is_some() {
+            config.logo = self.logo.clone();
+        }
+        if self.logo_image.is_some() {
+            config.logo_image = self.logo_image.clone();
+        }
+        if self.public_ip {
+            config.public_ip = Some(true);
+        }
+        if self.all_interfaces {
+            config.all_interfaces = Some(true);
+        }
+    }
+
+    pub fn usage() -> &'static str {
+        USAGE
+    }
+}
+
+/// Split a `--fields` list, rejecting names missing from the field registry
+fn parse_fields(list: &str) -> AppResult<Vec<String>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| match fields::find(name) {
+            Some(field) => Ok(field.name.to_string()),
+            None => Err(format!("unknown field '{name}' (see --list-fields)").into()),
+        })
+        .collect()
+}
@@ -0,0 +1,867 @@
+use crate::AppResult;
+use crate::health::Thresholds;
+use crate::panel::{InfoItem, Panel};
+use crate::system_info;
+use crate::template;
+#[cfg(feature = "tui")]
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// User configuration loaded from `config.toml`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Panels collapsed to their title bar, restored on the next launch
+    pub collapsed: Option<Vec<Panel>>,
+    /// Hardware panels left out of the full layout, toggled with the number keys
+    pub hidden: Option<Vec<Panel>>,
+    /// Order of the hardware panels, top to bottom; unlisted ones follow in the default order
+    pub sections: Option<Vec<String>>,
+    /// Disable the periodic refresh; stats only update when `r` is pressed
+    pub refresh_on_keypress_only: Option<bool>,
+    /// Time between refreshes: milliseconds or a duration such as `500ms`, `2s` or `1m`
+    pub interval: Option<String>,
+    /// Time an external command (lspci, wmic, system_profiler, ...) may run before it is killed
+    pub command_timeout: Option<String>,
+    /// Title bar template with `{field}` tokens, e.g. `"{hostname} · {os_name} {os_version}"`
+    pub title: Option<String>,
+    /// How tokens that name no field render in the title: `literal` or `empty`
+    pub title_unknown: Option<UnknownToken>,
+    /// Template printed by `--oneline`, with the same tokens as `title`
+    pub oneline: Option<String>,
+    /// Look up the public IP address, which sends a request to api.ipify.org
+    pub public_ip: Option<bool>,
+    /// List loopback interfaces in the Network panel too
+    pub all_interfaces: Option<bool>,
+    /// Force the compact or full layout instead of picking by terminal size
+    pub layout: Option<LayoutMode>,
+    /// Mount point shown on the Disk line of the compact layout
+    pub primary_disk: Option<String>,
+    /// Width of the logo panel, in percent of the terminal width
+    pub logo_width: Option<u16>,
+    /// Horizontal alignment of the logo within its panel
+    pub logo_align: Option<LogoAlign>,
+    /// Draw memory and disk usage as full-height gauges or one-line bars
+    pub bar_style: Option<BarStyle>,
+    /// Unit used to display temperatures (`c` or `f`)
+    pub temp_unit: Option<TempUnit>,
+    /// Write uptimes as `3d 4h 5m` or `3 days, 4 hours, 5 minutes`
+    pub uptime_format: Option<UptimeFormat>,
+    /// Count bytes in binary (KiB, 1024) or decimal (KB, 1000) steps
+    pub byte_unit: Option<ByteUnit>,
+    /// Draw with plain ASCII instead of emoji and box drawing; on by default when the locale isn't UTF-8
+    pub ascii: Option<bool>,
+    /// Draw without colors; also turned on by a non-empty `NO_COLOR`
+    pub no_color: Option<bool>,
+    /// Text file with ASCII or ANSI art shown in place of the built-in logo
+    pub logo: Option<PathBuf>,
+    /// Image rendered as braille art in place of the built-in logo
+    pub logo_image: Option<PathBuf>,
+    /// Color the braille logo from the image pixels instead of the theme logo color
+    pub logo_image_color: Option<bool>,
+    pub theme: ThemeConfig,
+    /// Colors of individual info lines, overriding the theme
+    pub colors: BTreeMap<InfoItem, String>,
+    pub thresholds: ThresholdConfig,
+}
+
+/// How the UI layout is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    /// Compact on small terminals, full otherwise
+    #[default]
+    Auto,
+    Compact,
+    Full,
+}
+
+impl FromStr for LayoutMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "auto" => Ok(Self::Auto),
+            "compact" => Ok(Self::Compact),
+            "full" => Ok(Self::Full),
+            other => Err(format!(
+                "invalid layout '{other}' (expected compact, full or auto)"
+            )),
+        }
+    }
+}
+
+/// Rendering of template tokens that match no field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownToken {
+    #[default]
+    Literal,
+    Empty,
+}
+
+/// How usage is drawn in the full layout; the compact layout always uses inline bars
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BarStyle {
+    #[default]
+    Gauge,
+    Inline,
+}
+
+/// Display unit for temperatures; readings are kept in Celsius internally
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TempUnit {
+    #[default]
+    C,
+    F,
+}
+
+impl FromStr for TempUnit {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "c" => Ok(Self::C),
+            "f" => Ok(Self::F),
+            other => Err(format!(
+                "invalid temperature unit '{other}' (expected c or f)"
+            )),
+        }
+    }
+}
+
+/// How uptimes are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UptimeFormat {
+    /// `3d 4h 5m`, or `45s` under a minute
+    #[default]
+    Compact,
+    /// `3 days, 4 hours, 5 minutes`
+    Verbose,
+}
+
+/// Steps in which byte counts are displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteUnit {
+    /// KiB, MiB, GiB: powers of 1024
+    #[default]
+    Binary,
+    /// KB, MB, GB: powers of 1000
+    Decimal,
+}
+
+impl ByteUnit {
+    /// The other unit, for the `u` key
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Binary => Self::Decimal,
+            Self::Decimal => Self::Binary,
+        }
+    }
+}
+
+/// Horizontal alignment of the logo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogoAlign {
+    #[default]
+    Left,
+    Center,
+}
+
+/// Default width of the logo panel, in percent
+pub const DEFAULT_LOGO_WIDTH: u16 = 40;
+
+/// Names of the built-in themes, in the order `t` cycles through them
+pub const THEME_PRESETS: [&str; 6] = [
+    "default",
+    "retro",
+    "mono",
+    "dracula",
+    "gruvbox",
+    "solarized",
+];
+
+/// Theme colors, given as color names (`"cyan"`) or hex strings (`"#8ec07c"`)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Built-in theme the other settings apply on top of (one of `THEME_PRESETS`)
+    pub preset: Option<String>,
+    pub title: Option<String>,
+    pub label: Option<String>,
+    pub accent: Option<String>,
+    pub logo: Option<String>,
+    pub muted: Option<String>,
+    pub background: Option<String>,
+    /// Border style: `plain`, `rounded`, `double` or `thick`
+    pub border: Option<String>,
+}
+
+impl ThemeConfig {
+    /// The built-in default theme as config values
+    #[cfg(feature = "tui")]
+    fn defaults() -> Self {
+        let theme = Theme::default();
+        let color = |color: ratatui::style::Color| Some(color.to_string().to_lowercase());
+
+        Self {
+            preset: Some("default".to_string()),
+            title: color(theme.title),
+            label: color(theme.label),
+            accent: color(theme.accent),
+            logo: color(theme.logo),
+            muted: color(theme.muted),
+            background: color(theme.background),
+            border: Some(format!("{:?}", theme.border).to_lowercase()),
+        }
+    }
+
+    /// Without the `tui` feature there is no theme to take defaults from
+    #[cfg(not(feature = "tui"))]
+    fn defaults() -> Self {
+        Self::default()
+    }
+}
+
+/// Usage percentages above which gauges turn red and non-interactive runs exit nonzero
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThresholdConfig {
+    pub memory: Option<f64>,
+    pub disk: Option<f64>,
+    /// Swap usage in percent; 0 flags any swap in use
+    pub swap: Option<f64>,
+    /// CPU and GPU temperature in °C, whatever `temp_unit` is
+    pub cpu_temp: Option<f64>,
+    /// 1-minute load average per logical CPU
+    pub load: Option<f64>,
+}
+
+/// Comments written above each key by `--init-config`
+const KEY_DOCS: &[(&str, &str)] = &[
+    (
+        "collapsed",
+        "Panels collapsed to their title bar (system, cpu, memory, battery, disk, gpu, network, processes)",
+    ),
+    (
+        "hidden",
+        "Hardware panels left out of the full layout (cpu, memory, battery, disk, gpu, network, processes)",
+    ),
+    (
+        "sections",
+        "Order of the hardware panels, top to bottom; panels left out follow in the default order",
+    ),
+    (
+        "refresh_on_keypress_only",
+        "Disable the periodic refresh; stats only update when `r` is pressed",
+    ),
+    (
+        "interval",
+        "Time between refreshes: milliseconds or a duration such as 500ms, 2s or 1m (at least 100ms)",
+    ),
+    (
+        "command_timeout",
+        "Time an external command (lspci, wmic, system_profiler, ...) may run before it is killed and its field reads as unknown",
+    ),
+    (
+        "title",
+        "Title bar template with {field} tokens; `rsysfetch --list-fields` lists the field names",
+    ),
+    (
+        "title_unknown",
+        "How tokens that name no field render in title and oneline: literal (kept as written) or empty",
+    ),
+    (
+        "oneline",
+        "Line printed by --oneline, with the same {field} tokens as title",
+    ),
+    (
+        "public_ip",
+        "Look up the public IP address (sends a request to api.ipify.org)",
+    ),
+    (
+        "all_interfaces",
+        "List loopback interfaces in the Network panel too",
+    ),
+    (
+        "layout",
+        "Layout to use: compact, full or auto (picked by terminal size)",
+    ),
+    (
+        "primary_disk",
+        "Mount point shown on the Disk line of the compact layout",
+    ),
+    (
+        "logo_width",
+        "Width of the logo panel, in percent of the terminal width",
+    ),
+    (
+        "logo_align",
+        "Horizontal alignment of the logo within its panel: left or center",
+    ),
+    (
+        "bar_style",
+        "Draw usage in the full layout as gauge (full-height gauges) or inline (one-line bars)",
+    ),
+    ("temp_unit", "Unit used to display temperatures: c or f"),
+    (
+        "uptime_format",
+        "Write uptimes as compact (3d 4h 5m) or verbose (3 days, 4 hours, 5 minutes)",
+    ),
+    (
+        "byte_unit",
+        "Show sizes in binary (KiB, steps of 1024) or decimal (KB, steps of 1000) units",
+    ),
+    (
+        "ascii",
+        "Draw with plain ASCII instead of emoji and box drawing (on by default when the locale isn't UTF-8)",
+    ),
+    (
+        "no_color",
+        "Draw without colors (also turned on by a non-empty NO_COLOR environment variable)",
+    ),
+    (
+        "logo",
+        "Text file with ASCII or ANSI art shown in place of the built-in logo",
+    ),
+    (
+        "logo_image",
+        "PNG or JPEG rendered as braille art in place of the built-in logo",
+    ),
+    (
+        "logo_image_color",
+        "Color the braille logo from the image pixels",
+    ),
+    (
+        "theme",
+        "Preset (default, retro, mono, dracula, gruvbox or solarized), colors by role as names or hex strings (\"#8ec07c\") and border style (plain, rounded, double or thick)",
+    ),
+    (
+        "colors",
+        "Colors of single lines, overriding the theme, e.g. cpu = \"red\" (os, kernel, host, user, security, uptime, power, packages, shell, terminal, cpu, memory, disk, gpu, displays, audio, network)",
+    ),
+    (
+        "thresholds",
+        "Limits above which values turn red and non-interactive runs exit with status 2 (usage in percent, swap = 0 flags any swap in use, cpu_temp, which also covers GPUs, in °C, load per logical CPU)",
+    ),
+];
+
+/// Keys without a default value, written as commented-out examples
+/// Closing note of `--init-config` on `[profiles.*]`, which has no default to show
+const PROFILES_DOC: &str = "
+# Named profiles override any of the keys above; pick one with --profile <name>.
+# A profile named `default` applies when --profile is not given.
+# [profiles.work]
+# interval = \"5s\"
+# theme.preset = \"mono\"
+";
+
+const KEY_EXAMPLES: &[(&str, &str)] = &[
+    ("title", "\"{hostname} · {os_name} {os_version}\""),
+    ("ascii", "true"),
+    ("logo", "\"/path/to/logo.txt\""),
+    ("logo_image", "\"/path/to/logo.png\""),
+];
+
+impl Config {
+    /// A config with every key set to the value used when it is missing
+    pub fn defaults() -> Self {
+        let thresholds = Thresholds::default();
+
+        Self {
+            collapsed: Some(Vec::new()),
+            hidden: Some(Vec::new()),
+            sections: Some(
+                Panel::HARDWARE
+                    .iter()
+                    .map(|panel| panel.name().to_string())
+                    .collect(),
+            ),
+            refresh_on_keypress_only: Some(false),
+            interval: Some("1s".to_string()),
+            command_timeout: Some("2s".to_string()),
+            title: None,
+            title_unknown: Some(UnknownToken::default()),
+            oneline: Some(template::DEFAULT_ONELINE.to_string()),
+            public_ip: Some(false),
+            all_interfaces: Some(false),
+            layout: Some(LayoutMode::default()),
+            primary_disk: Some(default_primary_disk().to_string()),
+            logo_width: Some(DEFAULT_LOGO_WIDTH),
+            logo_align: Some(LogoAlign::default()),
+            bar_style: Some(BarStyle::default()),
+            temp_unit: Some(TempUnit::default()),
+            uptime_format: Some(UptimeFormat::default()),
+            byte_unit: Some(ByteUnit::default()),
+            ascii: None,
+            no_color: Some(false),
+            logo: None,
+            logo_image: None,
+            logo_image_color: Some(false),
+            theme: ThemeConfig::defaults(),
+            colors: BTreeMap::new(),
+            thresholds: ThresholdConfig {
+                memory: Some(thresholds.memory),
+                disk: Some(thresholds.disk),
+                swap: Some(thresholds.swap),
+                cpu_temp: Some(thresholds.cpu_temp),
+                load: Some(thresholds.load),
+            },
+        }
+    }
+
+    /// The `command_timeout` setting, or the default when it is not set
+    pub fn command_timeout(&self) -> AppResult<Duration> {
+        match &self.command_timeout {
+            Some(timeout) => {
+                parse_duration(timeout).map_err(|err| format!("command_timeout: {err}").into())
+            }
+            None => Ok(system_info::DEFAULT_COMMAND_TIMEOUT),
+        }
+    }
+
+    /// Load the config file, falling back to defaults when it does not exist.
+    ///
+    /// The `[profiles.<name>]` table picked by `profile`, or `[profiles.default]`
+    /// when none is given, is applied on top of the base settings.
+    pub fn load(profile: Option<&str>) -> AppResult<Self> {
+        let content = config_path().and_then(|path| Some((fs::read_to_string(&path).ok()?, path)));
+        let Some((content, path)) = content else {
+            return match profile {
+                Some(name) => Err(format!("unknown profile '{name}' (no config file)").into()),
+                None => Ok(Self::default()),
+            };
+        };
+
+        Self::parse(&content, profile)
+            .map_err(|err| format!("invalid config {}: {err}", path.display()).into())
+    }
+
+    /// Parse config file contents, applying the selected profile
+    pub fn parse(content: &str, profile: Option<&str>) -> AppResult<Self> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        let mut profiles = match table.remove("profiles") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => return Err("`profiles` must be a table".into()),
+            None => toml::Table::new(),
+        };
+
+        let name = profile.unwrap_or("default");
+        match profiles.remove(name) {
+            Some(toml::Value::Table(overrides)) => merge_tables(&mut table, overrides),
+            Some(_) => return Err(format!("profile '{name}' must be a table").into()),
+            None if profile.is_some() => {
+                let names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                return Err(format!(
+                    "unknown profile '{name}' (available: {})",
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                )
+                .into());
+            }
+            None => {}
+        }
+
+        Ok(table.try_into()?)
+    }
+}
+
+/// Render the defaults as a fully commented `config.toml`
+pub fn default_config_toml() -> AppResult<String> {
+    let defaults = toml::to_string(&Config::defaults())?;
+    let doc = |key: &str| {
+        KEY_DOCS
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, doc)| format!("\n# {doc}\n"))
+            .unwrap_or_default()
+    };
+
+    let mut content = String::from(
+        "# rsysfetch configuration\n# Every key is optional; uncomment a line to change its default.\n",
+    );
+    for (key, example) in KEY_EXAMPLES {
+        content.push_str(&doc(key));
+        content.push_str(&format!("# {key} = {example}\n"));
+    }
+
+    let mut in_table = false;
+    for line in defaults.lines() {
+        if let Some(table) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            in_table = true;
+            content.push_str(&doc(table));
+        } else if !in_table {
+            if let Some((key, _)) = line.split_once(" = ") {
+                content.push_str(&doc(key));
+            }
+        }
+
+        if !line.is_empty() {
+            content.push_str(&format!("# {line}\n"));
+        }
+    }
+    content.push_str(PROFILES_DOC);
+
+    Ok(content)
+}
+
+/// Every resolved setting as a `key = value` line with where it came from
+/// (`default`, `file` or `cli`)
+pub fn resolved_settings(file: &Config, cli: &Config) -> AppResult<Vec<(String, &'static str)>> {
+    let mut resolved = file.clone();
+    merge(&mut resolved, cli)?;
+    if let Some(interval) = &resolved.interval {
+        parse_interval(interval)?;
+    }
+    resolved.command_timeout()?;
+
+    let mut defaults = Vec::new();
+    flatten(
+        "",
+        &toml::Value::try_from(Config::defaults())?,
+        &mut defaults,
+    );
+    let mut from_file = Vec::new();
+    flatten("", &toml::Value::try_from(file)?, &mut from_file);
+    let mut from_cli = Vec::new();
+    flatten("", &toml::Value::try_from(cli)?, &mut from_cli);
+
+    // Keys without a default (e.g. `logo_image`) only show up when set
+    let mut keys: Vec<&String> = defaults.iter().map(|(key, _)| key).collect();
+    for (key, _) in from_file.iter().chain(&from_cli) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    let lookup = |settings: &[(String, toml::Value)], key: &str| {
+        settings
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.to_string())
+    };
+    Ok(keys
+        .iter()
+        .filter_map(|key| {
+            let (value, source) = lookup(&from_cli, key)
+                .map(|value| (value, "cli"))
+                .or_else(|| lookup(&from_file, key).map(|value| (value, "file")))
+                .or_else(|| lookup(&defaults, key).map(|value| (value, "default")))?;
+            Some((format!("{key} = {value}"), source))
+        })
+        .collect())
+}
+
+/// Align `resolved_settings` rows with their source as a trailing comment
+pub fn format_settings(rows: &[(String, &str)]) -> String {
+    let width = rows.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(line, source)| format!("{line:width$}  # {source}\n"))
+        .collect()
+}
+
+/// Overlay the keys set in `overrides` onto `config`
+fn merge(config: &mut Config, overrides: &Config) -> AppResult<()> {
+    let mut base = toml::Table::try_from(&*config)?;
+    let overrides = toml::Table::try_from(overrides)?;
+    merge_tables(&mut base, overrides);
+    *config = base.try_into()?;
+    Ok(())
+}
+
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(value)) => {
+                merge_tables(base, value);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Flatten nested tables into `table.key` paths, skipping empty tables
+fn flatten(prefix: &str, value: &toml::Value, settings: &mut Vec<(String, toml::Value)>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, value, settings);
+            }
+        }
+        value => settings.push((prefix.to_string(), value.clone())),
+    }
+}
+
+/// Write the default config to the config path, refusing to replace an existing file unless `force`
+pub fn init_config(force: bool) -> AppResult<PathBuf> {
+    let path = config_path().ok_or("no config directory available")?;
+    if !force && path.exists() {
+        return Err(format!(
+            "{} already exists (pass --force to overwrite it)",
+            path.display()
+        )
+        .into());
+    }
+
+    write_file(&path, &default_config_toml()?)?;
+    Ok(path)
+}
+
+/// Persist the collapsed panels, keeping the rest of the config file untouched
+pub fn save_collapsed(collapsed: &HashSet<Panel>) -> AppResult<()> {
+    save_panels("collapsed", collapsed)
+}
+
+/// Persist the hidden panels, keeping the rest of the config file untouched
+pub fn save_hidden(hidden: &HashSet<Panel>) -> AppResult<()> {
+    save_panels("hidden", hidden)
+}
+
+fn save_panels(key: &str, panels: &HashSet<Panel>) -> AppResult<()> {
+    let path = config_path().ok_or("no config directory available")?;
+
+    let mut document = match fs::read_to_string(&path) {
+        Ok(content) => content.parse::<toml_edit::DocumentMut>()?,
+        Err(_) => toml_edit::DocumentMut::new(),
+    };
+
+    // Keep a stable order so the file does not churn between saves
+    let names: toml_edit::Array = Panel::ALL
+        .iter()
+        .filter(|panel| panels.contains(panel))
+        .map(|panel| panel.name())
+        .collect();
+    document[key] = toml_edit::value(names);
+
+    write_file(&path, &document.to_string())
+}
+
+fn write_file(path: &Path, content: &str) -> AppResult<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, content)?;
+
+    Ok(())
+}
+
+/// Shortest refresh interval; sampling more often mostly measures rsysfetch itself
+pub const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Parse a refresh interval (see `parse_duration`), rejecting ones below `MIN_INTERVAL`
+pub fn parse_interval(value: &str) -> Result<Duration, String> {
+    let interval = parse_duration(value)?;
+    if interval < MIN_INTERVAL {
+        return Err(format!(
+            "invalid interval '{}' (must be at least {}ms)",
+            value.trim(),
+            MIN_INTERVAL.as_millis()
+        ));
+    }
+    Ok(interval)
+}
+
+/// Parse a refresh interval: a bare number of milliseconds, or a number with an
+/// `ms`, `s`, `m` or `h` suffix (`500ms`, `1.5s`, `2m`)
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let invalid = || format!("invalid interval '{value}' (expected e.g. 500ms, 2s or 1m)");
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    let seconds = match unit.trim() {
+        "" | "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return Err(invalid()),
+    };
+
+    let duration = Duration::try_from_secs_f64(seconds).map_err(|_| invalid())?;
+    if duration.is_zero() {
+        return Err(format!(
+            "invalid interval '{value}' (must be greater than zero)"
+        ));
+    }
+    Ok(duration)
+}
+
+/// Root of the system drive
+pub fn default_primary_disk() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "C:\\"
+    } else {
+        "/"
+    }
+}
+
+/// Get the config file path (`~/.config/rsysfetch/config.toml` on Unix)
+pub fn config_path() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        let dir = PathBuf::from(env::var_os("APPDATA")?);
+        return Some(dir.join("rsysfetch").join("config.toml"));
+    }
+    config_path_from(env::var_os("XDG_CONFIG_HOME"), env::var_os("HOME"))
+}
+
+/// The Unix config path for the given `XDG_CONFIG_HOME` and `HOME`; an empty
+/// `XDG_CONFIG_HOME` counts as unset
+fn config_path_from(xdg_config_home: Option<OsString>, home: Option<OsString>) -> Option<PathBuf> {
+    let dir = xdg_config_home
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home.map(|home| PathBuf::from(home).join(".config")))?;
+    Some(dir.join("rsysfetch").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_reads_suffixes() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration(" 3 s "), Ok(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn parse_duration_treats_bare_numbers_as_milliseconds() {
+        assert_eq!(parse_duration("250"), Ok(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn parse_duration_rejects_bad_values() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("5d").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("0s").is_err());
+    }
+
+    #[test]
+    fn profile_overrides_base_keys_and_keeps_the_rest() {
+        let content = r#"
+interval = "2s"
+public_ip = true
+
+[theme]
+preset = "dracula"
+
+[profiles.work]
+interval = "500ms"
+
+[profiles.work.theme]
+preset = "mono"
+"#;
+        let config = Config::parse(content, Some("work")).unwrap();
+        assert_eq!(config.interval.as_deref(), Some("500ms"));
+        assert_eq!(config.public_ip, Some(true));
+        assert_eq!(config.theme.preset.as_deref(), Some("mono"));
+
+        let config = Config::parse(content, None).unwrap();
+        assert_eq!(config.interval.as_deref(), Some("2s"));
+        assert_eq!(config.theme.preset.as_deref(), Some("dracula"));
+    }
+
+    #[test]
+    fn unknown_profile_lists_the_available_ones() {
+        let error = Config::parse("[profiles.work]\n", Some("home")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown profile 'home' (available: work)"
+        );
+    }
+
+    #[test]
+    fn config_path_prefers_xdg_config_home() {
+        assert_eq!(
+            config_path_from(Some("/xdg".into()), Some("/home/demo".into())),
+            Some(PathBuf::from("/xdg/rsysfetch/config.toml"))
+        );
+    }
+
+    #[test]
+    fn config_path_falls_back_to_home() {
+        let expected = Some(PathBuf::from("/home/demo/.config/rsysfetch/config.toml"));
+        assert_eq!(config_path_from(None, Some("/home/demo".into())), expected);
+        assert_eq!(
+            config_path_from(Some("".into()), Some("/home/demo".into())),
+            expected
+        );
+        assert_eq!(config_path_from(None, None), None);
+    }
+
+    #[test]
+    fn resolved_settings_report_their_source() {
+        let file = Config::parse("interval = \"2s\"\nbyte_unit = \"decimal\"\n", None).unwrap();
+        let cli = Config {
+            interval: Some("500ms".to_string()),
+            ..Config::default()
+        };
+        let settings = resolved_settings(&file, &cli).unwrap();
+        let source = |line: &str| {
+            settings
+                .iter()
+                .find(|(setting, _)| setting == line)
+                .map(|(_, source)| *source)
+        };
+
+        assert_eq!(source("interval = \"500ms\""), Some("cli"));
+        assert_eq!(source("byte_unit = \"decimal\""), Some("file"));
+        assert_eq!(source("public_ip = false"), Some("default"));
+    }
+
+    #[test]
+    fn every_default_key_is_documented() {
+        let defaults = toml::Value::try_from(Config::defaults()).unwrap();
+        let keys: Vec<&str> = defaults
+            .as_table()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .chain(KEY_EXAMPLES.iter().map(|(key, _)| *key))
+            .collect();
+        let documented = |key: &str| KEY_DOCS.iter().any(|(name, _)| *name == key);
+
+        for key in &keys {
+            assert!(documented(key), "`{key}` has no KEY_DOCS entry");
+        }
+        for (name, _) in KEY_DOCS {
+            assert!(
+                keys.contains(name),
+                "KEY_DOCS documents unknown key `{name}`"
+            );
+        }
+    }
+}
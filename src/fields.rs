@@ -0,0 +1,180 @@
+/// A top-level value of `SystemInfo` that can be selected with `--fields`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: &'static str,
+    pub description: &'static str,
+}
+
+const fn field(name: &'static str, kind: &'static str, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        description,
+    }
+}
+
+/// Every collected field, in output order
+pub const FIELDS: &[Field] = &[
+    field("os_name", "string?", "Operating system name"),
+    field("os_version", "string?", "Operating system version"),
+    field(
+        "os_arch",
+        "string",
+        "CPU architecture the binary was built for",
+    ),
+    field("kernel_version", "string?", "Kernel release"),
+    field(
+        "kernel_build",
+        "string?",
+        "Kernel build details (compiler, date)",
+    ),
+    field(
+        "kernel_modules",
+        "integer?",
+        "Loaded kernel modules (Linux)",
+    ),
+    field("hostname", "string?", "Host name"),
+    field(
+        "host_model",
+        "string?",
+        "Machine model or product name; absent in most VMs",
+    ),
+    field("is_virtual", "bool", "Running as a virtual machine guest"),
+    field(
+        "hypervisor",
+        "string?",
+        "Hypervisor running the guest (e.g. KVM, VMware), when it can be named",
+    ),
+    field("username", "string?", "Current user"),
+    field("security", "map?", "Secure Boot state and TPM version"),
+    field("uptime_seconds", "integer", "Time since boot, in seconds"),
+    field(
+        "boot_time",
+        "string?",
+        "When the machine booted, as local YYYY-MM-DD HH:MM",
+    ),
+    field(
+        "load_average",
+        "list?",
+        "1, 5 and 15 minute load averages (not on Windows)",
+    ),
+    field(
+        "cpus",
+        "list",
+        "CPU model, vendor ID (e.g. AuthenticAMD), core count and frequency (MHz)",
+    ),
+    field("cpu_usage", "float", "Load across all cores, in percent"),
+    field(
+        "cpu_frequency",
+        "integer",
+        "Current clock of the first core (MHz), 0 when not reported",
+    ),
+    field("cpu_temp", "float?", "Hottest CPU sensor reading, in °C"),
+    field(
+        "core_usage",
+        "list",
+        "Load of each logical core, in percent",
+    ),
+    field("cpu_governor", "string?", "CPU frequency governor (Linux)"),
+    field(
+        "power_profile",
+        "string?",
+        "Active power profile from powerprofilesctl (Linux)",
+    ),
+    field("memory_total", "integer", "Total memory, in bytes"),
+    field("memory_used", "integer", "Used memory, in bytes"),
+    field(
+        "memory_pressure",
+        "string?",
+        "Memory pressure level (macOS)",
+    ),
+    field(
+        "swap_total",
+        "integer",
+        "Total swap, in bytes; 0 without swap",
+    ),
+    field("swap_used", "integer", "Used swap, in bytes"),
+    field(
+        "cgroup",
+        "map?",
+        "Container memory (bytes) and CPU limits below the host's",
+    ),
+    field(
+        "gpus",
+        "list",
+        "GPU name, vendor, driver version, temperature (°C) and VRAM used (NVIDIA only) and total, in bytes",
+    ),
+    field(
+        "displays",
+        "list",
+        "Connected displays with resolution and primary flag",
+    ),
+    field("monitor_count", "integer", "Number of connected displays"),
+    field("audio_device", "string?", "Default audio output device"),
+    field("local_ip", "string?", "Local IP address"),
+    field(
+        "public_ip",
+        "string?",
+        "Public IP address, only looked up with --public-ip",
+    ),
+    field(
+        "interfaces",
+        "list",
+        "Network interfaces with addresses, MAC and rx/tx rates (bytes/s)",
+    ),
+    field("disks", "list", "Mounted disks with space and inode usage"),
+    field(
+        "on_ac_power",
+        "bool?",
+        "Running on mains power; unknown without an adapter",
+    ),
+    field(
+        "top_processes",
+        "list",
+        "Up to five processes using the most memory: name, pid, cpu (percent of one core), memory (bytes)",
+    ),
+    field(
+        "battery",
+        "map?",
+        "Battery percent, state and seconds remaining; absent on desktops",
+    ),
+    field("lid_open", "bool?", "Laptop lid state (Linux)"),
+    field(
+        "packages",
+        "list",
+        "Installed package count per manager, as [name, count] pairs",
+    ),
+    field("shell", "string?", "Login shell"),
+    field("terminal", "string?", "Terminal emulator"),
+    field("errors", "list", "Probes that failed and why, as strings"),
+];
+
+/// Look up a field by name
+pub fn find(name: &str) -> Option<&'static Field> {
+    FIELDS.iter().find(|field| field.name == name)
+}
+
+/// The `--list-fields` table: name, type and description per line
+pub fn list() -> String {
+    let name_width = FIELDS
+        .iter()
+        .map(|field| field.name.len())
+        .max()
+        .unwrap_or(0);
+    let kind_width = FIELDS
+        .iter()
+        .map(|field| field.kind.len())
+        .max()
+        .unwrap_or(0);
+
+    FIELDS
+        .iter()
+        .map(|field| {
+            format!(
+                "{:name_width$}  {:kind_width$}  {}\n",
+                field.name, field.kind, field.description
+            )
+        })
+        .collect()
+}
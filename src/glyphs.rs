@@ -0,0 +1,124 @@
+use crate::panel::Panel;
+use ratatui::symbols::{bar, border};
+use std::env;
+
+/// Every non-ASCII symbol the interface draws, so limited terminals can swap them all at once
+#[derive(Debug)]
+pub struct Glyphs {
+    /// Icon (with its trailing space) in front of each panel title, in `Panel::ALL` order
+    panel_icons: [&'static str; 8],
+    pub ac_power: &'static str,
+    pub on_battery: &'static str,
+    pub paused: &'static str,
+    /// In front of the copyright line under the built-in logo
+    pub credit_icon: &'static str,
+    /// Filled and empty cells of the inline usage bars
+    pub bar_filled: &'static str,
+    pub bar_empty: &'static str,
+    /// Replaces the full blocks gauges are filled with
+    pub gauge_fill: Option<&'static str>,
+    /// One cell of the per-core history grid
+    pub core_cell: &'static str,
+    pub download: &'static str,
+    pub upload: &'static str,
+    /// Marks the column the process table is sorted by
+    pub sorted: &'static str,
+    /// Stands in for a value that could not be determined
+    pub unknown: &'static str,
+    pub pending: &'static str,
+    /// Between the parts of a line, spaces included
+    pub separator: &'static str,
+    pub scroll_keys: &'static str,
+    /// Replaces the theme's border style when set
+    pub border: Option<border::Set>,
+    pub sparkline: bar::Set,
+}
+
+/// Emoji, block elements and box drawing
+pub const UNICODE: Glyphs = Glyphs {
+    panel_icons: ["🖥️  ", "🔥 ", "💾 ", "🔋 ", "💿 ", "🎮 ", "🌐 ", "📊 "],
+    ac_power: "🔌 AC",
+    on_battery: "🔋 Battery",
+    paused: "⏸ PAUSED",
+    credit_icon: "🦀 ",
+    bar_filled: "█",
+    bar_empty: "░",
+    gauge_fill: None,
+    core_cell: "█",
+    download: "↓",
+    upload: "↑",
+    sorted: " ▼",
+    unknown: "—",
+    pending: "…",
+    separator: " · ",
+    scroll_keys: "↑/↓",
+    border: None,
+    sparkline: bar::NINE_LEVELS,
+};
+
+/// Plain ASCII for terminals without UTF-8 or emoji fonts (`--ascii`)
+pub const ASCII: Glyphs = Glyphs {
+    panel_icons: [""; 8],
+    ac_power: "AC",
+    on_battery: "Battery",
+    paused: "PAUSED",
+    credit_icon: "",
+    bar_filled: "#",
+    bar_empty: "-",
+    gauge_fill: Some("#"),
+    core_cell: "#",
+    download: "rx",
+    upload: "tx",
+    sorted: " v",
+    unknown: "-",
+    pending: "...",
+    separator: " | ",
+    scroll_keys: "Up/Down",
+    border: Some(border::Set {
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        vertical_left: "|",
+        vertical_right: "|",
+        horizontal_top: "-",
+        horizontal_bottom: "-",
+    }),
+    sparkline: bar::Set {
+        full: "#",
+        seven_eighths: "#",
+        three_quarters: "#",
+        five_eighths: "=",
+        half: "=",
+        three_eighths: "=",
+        one_quarter: ".",
+        one_eighth: ".",
+        empty: " ",
+    },
+};
+
+impl Glyphs {
+    /// The ASCII table when `ascii` is set, the Unicode one otherwise
+    pub fn get(ascii: bool) -> &'static Glyphs {
+        if ascii { &ASCII } else { &UNICODE }
+    }
+
+    pub fn panel_icon(&self, panel: Panel) -> &'static str {
+        let index = Panel::ALL.iter().position(|p| *p == panel).unwrap_or(0);
+        self.panel_icons[index]
+    }
+}
+
+/// Whether the locale asks for something other than UTF-8, e.g. `LANG=C` over SSH.
+///
+/// The first of `LC_ALL`, `LC_CTYPE` and `LANG` that is set decides; with none set
+/// (as on Windows) the terminal is assumed to cope.
+pub fn locale_lacks_utf8() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+        .is_some_and(|locale| {
+            let locale = locale.to_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        })
+}
@@ -0,0 +1,187 @@
+use crate::config::ThresholdConfig;
+use crate::system_info::SystemInfo;
+
+/// Exit code of non-interactive runs when a threshold is breached
+pub const EXIT_THRESHOLD_BREACHED: i32 = 2;
+
+/// Usage limits in percent, temperatures in °C, load per logical CPU
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    pub memory: f64,
+    pub disk: f64,
+    /// Any swap use above this share of the swap space; 0 flags swap in use at all
+    pub swap: f64,
+    pub cpu_temp: f64,
+    /// 1-minute load average divided by the logical CPU count; 1.0 is load above the core count
+    pub load: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            memory: 95.0,
+            disk: 90.0,
+            swap: 0.0,
+            cpu_temp: 85.0,
+            load: 1.0,
+        }
+    }
+}
+
+/// A current value that has a threshold, in the unit of that threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reading {
+    /// Memory usage in percent
+    Memory(f64),
+    /// Disk usage in percent
+    Disk(f64),
+    /// Swap usage in percent
+    Swap(f64),
+    /// CPU temperature in °C
+    CpuTemp(f64),
+    /// GPU temperature in °C, held to the CPU temperature threshold
+    GpuTemp(f64),
+    /// 1-minute load average per logical CPU
+    Load(f64),
+}
+
+impl Thresholds {
+    /// Build the thresholds from the config, keeping the default for unset values
+    pub fn from_config(config: &ThresholdConfig) -> Self {
+        let default = Self::default();
+        Self {
+            memory: config.memory.unwrap_or(default.memory),
+            disk: config.disk.unwrap_or(default.disk),
+            swap: config.swap.unwrap_or(default.swap),
+            cpu_temp: config.cpu_temp.unwrap_or(default.cpu_temp),
+            load: config.load.unwrap_or(default.load),
+        }
+    }
+
+    /// Whether the reading is above its threshold
+    pub fn exceeded(&self, reading: Reading) -> bool {
+        match reading {
+            Reading::Memory(usage) => usage > self.memory,
+            Reading::Disk(usage) => usage > self.disk,
+            Reading::Swap(usage) => usage > self.swap,
+            Reading::CpuTemp(temp) | Reading::GpuTemp(temp) => temp > self.cpu_temp,
+            Reading::Load(load) => load > self.load,
+        }
+    }
+
+    /// Describe every value above its threshold
+    pub fn breaches(&self, info: &SystemInfo) -> Vec<String> {
+        let mut breaches = Vec::new();
+
+        let memory = percent(info.memory_used, info.memory_total);
+        if self.exceeded(Reading::Memory(memory)) {
+            breaches.push(format!(
+                "memory at {memory:.0}% (threshold {}%)",
+                self.memory
+            ));
+        }
+
+        let swap = percent(info.swap_used, info.swap_total);
+        if self.exceeded(Reading::Swap(swap)) {
+            breaches.push(format!("swap at {swap:.0}% (threshold {}%)", self.swap));
+        }
+
+        for disk in &info.disks {
+            let usage = percent(disk.used, disk.total);
+            if self.exceeded(Reading::Disk(usage)) {
+                breaches.push(format!(
+                    "disk {} at {usage:.0}% (threshold {}%)",
+                    disk.mount_point, self.disk
+                ));
+            }
+        }
+
+        if let Some(temp) = info
+            .cpu_temp
+            .filter(|temp| self.exceeded(Reading::CpuTemp(f64::from(*temp))))
+        {
+            breaches.push(format!(
+                "cpu temperature at {temp:.0}°C (threshold {}°C)",
+                self.cpu_temp
+            ));
+        }
+
+        if let Some(load) = load_per_cpu(info).filter(|load| self.exceeded(Reading::Load(*load))) {
+            breaches.push(format!(
+                "load at {load:.2} per CPU (threshold {})",
+                self.load
+            ));
+        }
+
+        for gpu in &info.gpus {
+            if let Some(temp) = gpu
+                .temperature
+                .filter(|temp| self.exceeded(Reading::GpuTemp(f64::from(*temp))))
+            {
+                breaches.push(format!(
+                    "gpu {} temperature at {temp:.0}°C (threshold {}°C)",
+                    gpu.name, self.cpu_temp
+                ));
+            }
+        }
+
+        breaches
+    }
+}
+
+/// `used` as a percentage of `total`, or 0 when the total is unknown
+pub fn percent(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        used as f64 * 100.0 / total as f64
+    } else {
+        0.0
+    }
+}
+
+/// The 1-minute load average divided by the logical CPU count, when both are known
+pub fn load_per_cpu(info: &SystemInfo) -> Option<f64> {
+    let (one, _, _) = info.load_average?;
+    let cpus = match info.core_usage.len() {
+        0 => info.cpus.iter().map(|cpu| cpu.cores).sum(),
+        cpus => cpus,
+    };
+    (cpus > 0).then(|| one / cpus as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_in_use_is_flagged_by_default() {
+        let thresholds = Thresholds::default();
+        assert!(!thresholds.exceeded(Reading::Swap(0.0)));
+        assert!(thresholds.exceeded(Reading::Swap(0.5)));
+
+        let config = ThresholdConfig {
+            swap: Some(50.0),
+            ..ThresholdConfig::default()
+        };
+        assert!(!Thresholds::from_config(&config).exceeded(Reading::Swap(6.0)));
+    }
+
+    #[test]
+    fn load_is_held_to_the_core_count() {
+        let thresholds = Thresholds::default();
+        assert!(!thresholds.exceeded(Reading::Load(1.0)));
+        assert!(thresholds.exceeded(Reading::Load(1.25)));
+    }
+
+    #[test]
+    fn load_per_cpu_divides_by_the_logical_cpus() {
+        let mut info = SystemInfo::load_mock(std::path::Path::new("fixtures/demo.json")).unwrap();
+        info.load_average = Some((8.0, 4.0, 2.0));
+        assert_eq!(load_per_cpu(&info), Some(0.5));
+
+        info.core_usage.clear();
+        assert_eq!(load_per_cpu(&info), Some(0.5));
+
+        info.load_average = None;
+        assert_eq!(load_per_cpu(&info), None);
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::VecDeque;
+
+/// A bounded series of samples, oldest first
+#[derive(Debug, Clone)]
+pub struct History {
+    samples: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append a sample, dropping the oldest one when full
+    pub fn push(&mut self, sample: u64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// The most recent `count` samples, oldest first
+    pub fn latest(&self, count: usize) -> Vec<u64> {
+        let skip = self.samples.len().saturating_sub(count);
+        self.samples.iter().skip(skip).copied().collect()
+    }
+}
+
+/// Per-core load samples, one column per refresh, oldest first
+#[derive(Debug, Clone)]
+pub struct CoreHistory {
+    columns: VecDeque<Vec<f32>>,
+    capacity: usize,
+}
+
+impl CoreHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            columns: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append one sample per core, starting over when the core count changes
+    pub fn push(&mut self, column: Vec<f32>) {
+        if self
+            .columns
+            .back()
+            .is_some_and(|last| last.len() != column.len())
+        {
+            self.columns.clear();
+        }
+        if self.columns.len() == self.capacity {
+            self.columns.pop_front();
+        }
+        self.columns.push_back(column);
+    }
+
+    /// Number of cores in the stored samples
+    pub fn cores(&self) -> usize {
+        self.columns.back().map_or(0, Vec::len)
+    }
+
+    /// The most recent `count` columns, oldest first
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &[f32]> {
+        let skip = self.columns.len().saturating_sub(count);
+        self.columns.iter().skip(skip).map(Vec::as_slice)
+    }
+}
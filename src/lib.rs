@@ -0,0 +1,26 @@
+use std::error::Error;
+
+#[cfg(feature = "tui")]
+pub mod app;
+pub mod cli;
+pub mod config;
+pub mod fields;
+#[cfg(feature = "tui")]
+pub mod glyphs;
+pub mod health;
+pub mod history;
+#[cfg(feature = "tui")]
+pub mod logo;
+pub mod output;
+pub mod panel;
+#[cfg(feature = "tui")]
+pub mod print;
+pub mod record;
+pub mod system_info;
+pub mod template;
+#[cfg(feature = "tui")]
+pub mod theme;
+#[cfg(feature = "tui")]
+pub mod ui;
+
+pub type AppResult<T> = Result<T, Box<dyn Error>>;
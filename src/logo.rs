@@ -1,3 +1,6 @@
+use crate::glyphs::Glyphs;
+use crate::theme::{self, Theme};
+use image::{DynamicImage, GenericImageView, imageops::FilterType};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
@@ -22,13 +25,98 @@ const LOGO: &[&str] = &[
     "                                              ",
 ];
 
-pub fn get_logo() -> Vec<Line<'static>> {
+const UBUNTU: &[&str] = &[
+    "             _",
+    "         ---(_)",
+    "     _/  ---  \\",
+    "    (_) |   |",
+    "      \\  --- _/",
+    "         ---(_)",
+];
+
+const ARCH: &[&str] = &[
+    "          /\\",
+    "         /  \\",
+    "        /\\   \\",
+    "       /      \\",
+    "      /   ,,   \\",
+    "     /   |  |  -\\",
+    "    /_-''    ''-_\\",
+];
+
+const FEDORA: &[&str] = &[
+    "          ,'''''.",
+    "         |   ,.  |",
+    "         |  |  '_'",
+    "    ,....|  |..",
+    "  .'  ,_;|   ..'",
+    "  |  |   |  |",
+    "  |  ',_,'  |",
+    "   '.     ,'",
+    "     '''''",
+];
+
+const DEBIAN: &[&str] = &[
+    "      _____",
+    "     /  __ \\",
+    "    |  /    |",
+    "    |  \\___-",
+    "    -_",
+    "      --_",
+];
+
+const WINDOWS: &[&str] = &[
+    "    ▄▄▄▄▄▄▄▄▄ ▄▄▄▄▄▄▄▄▄",
+    "    █████████ █████████",
+    "    █████████ █████████",
+    "    █████████ █████████",
+    "    ▄▄▄▄▄▄▄▄▄ ▄▄▄▄▄▄▄▄▄",
+    "    █████████ █████████",
+    "    █████████ █████████",
+    "    ▀▀▀▀▀▀▀▀▀ ▀▀▀▀▀▀▀▀▀",
+];
+
+const MACOS: &[&str] = &[
+    "            .:'",
+    "        __ :'__",
+    "     .'`  `-'  ``.",
+    "    :          .-'",
+    "    :         :",
+    "     :         `-;",
+    "      `.__.-.__.'",
+];
+
+/// Logos by a word of the lowercase OS name; the first match wins
+const OS_LOGOS: &[(&str, &[&str])] = &[
+    ("ubuntu", UBUNTU),
+    ("arch", ARCH),
+    ("fedora", FEDORA),
+    ("debian", DEBIAN),
+    ("windows", WINDOWS),
+    ("darwin", MACOS),
+    ("macos", MACOS),
+];
+
+/// The logo of the detected OS, or the crab when there is none for it
+pub fn get_logo_for(os_name: Option<&str>, theme: &Theme, glyphs: &Glyphs) -> Vec<Line<'static>> {
+    let os_name = os_name.unwrap_or_default().to_lowercase();
+    let logo = OS_LOGOS
+        .iter()
+        .find(|(name, _)| os_name.contains(name))
+        .map_or(LOGO, |(_, logo)| *logo);
+
+    // Pad every row to the widest so centering keeps the art together
+    let width = logo
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
     let mut lines = Vec::new();
 
-    for line in LOGO {
+    for line in logo {
         lines.push(Line::from(Span::styled(
-            *line,
-            Style::default().fg(Color::Red),
+            format!("{line:<width$}"),
+            Style::default().fg(theme.logo),
         )));
     }
 
@@ -41,10 +129,216 @@ pub fn get_logo() -> Vec<Line<'static>> {
     )));
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "🦀 Copyright (C) 2025 Stepfen Shawn",
-        Style::default().fg(Color::Yellow),
+        format!("{}Copyright (C) 2025 Stepfen Shawn", glyphs.credit_icon),
+        Style::default().fg(theme.accent),
     )));
     lines.push(Line::from(""));
 
     lines
 }
+
+/// Bit of each dot in a braille cell, indexed by `[row][column]`
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Render an image as braille art fitting `width` x `height` cells, keeping its aspect ratio
+pub fn braille_logo(
+    image: &DynamicImage,
+    width: u16,
+    height: u16,
+    colorize: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+
+    // Each cell holds 2x4 dots, which are roughly square on a typical terminal font
+    let image = image.resize(
+        u32::from(width) * 2,
+        u32::from(height) * 4,
+        FilterType::Triangle,
+    );
+    let (pixel_width, pixel_height) = image.dimensions();
+
+    // Transparent images light the opaque pixels, opaque ones the brighter than average
+    let has_alpha = image.color().has_alpha();
+    let luma = image.to_luma_alpha8();
+    let mean = luma.pixels().map(|pixel| u64::from(pixel[0])).sum::<u64>()
+        / u64::from((pixel_width * pixel_height).max(1));
+    let lit = |x: u32, y: u32| {
+        let pixel = luma.get_pixel(x, y);
+        if has_alpha {
+            pixel[1] >= 128
+        } else {
+            u64::from(pixel[0]) > mean
+        }
+    };
+
+    let truecolor = theme::supports_truecolor();
+    let rgb = image.to_rgb8();
+
+    (0..pixel_height.div_ceil(4))
+        .map(|row| {
+            let spans: Vec<Span<'static>> = (0..pixel_width.div_ceil(2))
+                .map(|column| {
+                    let mut bits = 0;
+                    let mut sum = [0u32; 3];
+                    let mut count = 0;
+
+                    for (dy, dots) in BRAILLE_DOTS.iter().enumerate() {
+                        for (dx, bit) in dots.iter().enumerate() {
+                            let (x, y) = (column * 2 + dx as u32, row * 4 + dy as u32);
+                            if x < pixel_width && y < pixel_height && lit(x, y) {
+                                bits |= bit;
+                                let pixel = rgb.get_pixel(x, y);
+                                for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                                    *total += u32::from(channel);
+                                }
+                                count += 1;
+                            }
+                        }
+                    }
+
+                    let symbol = char::from_u32(0x2800 + bits).unwrap_or(' ').to_string();
+                    let color = if colorize && count > 0 {
+                        let [r, g, b] = sum.map(|total| (total / count) as u8);
+                        let color = Color::Rgb(r, g, b);
+                        if truecolor {
+                            color
+                        } else {
+                            theme::to_ansi(color)
+                        }
+                    } else {
+                        theme.logo
+                    };
+
+                    Span::styled(symbol, Style::default().fg(color))
+                })
+                .collect();
+
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Parse text art with embedded SGR escapes (`ESC[...m`) into styled lines
+pub fn parse_ansi(text: &str) -> Vec<Line<'static>> {
+    text.lines()
+        .scan(Style::default(), |style, line| {
+            Some(parse_ansi_line(&line.replace('\t', "    "), style))
+        })
+        .collect()
+}
+
+/// Parse one line, carrying the style over from (and on to) neighbouring lines
+fn parse_ansi_line(line: &str, style: &mut Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            text.push(ch);
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+
+        // Collect the parameters up to the final byte; anything but `m` is dropped
+        let mut params = String::new();
+        let mut last = None;
+        for ch in chars.by_ref() {
+            if ('\x40'..='\x7e').contains(&ch) {
+                last = Some(ch);
+                break;
+            }
+            params.push(ch);
+        }
+        if last != Some('m') {
+            continue;
+        }
+
+        if !text.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut text), *style));
+        }
+        *style = apply_sgr(*style, &params);
+    }
+    if !text.is_empty() {
+        spans.push(Span::styled(text, *style));
+    }
+    Line::from(spans)
+}
+
+/// Apply the `;`-separated SGR parameters to `style`
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u8> = params
+        .split(';')
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut codes = codes.into_iter();
+
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            40..=47 => style.bg(Color::Indexed(code - 40)),
+            100..=107 => style.bg(Color::Indexed(code - 100 + 8)),
+            39 => style.fg(Color::Reset),
+            49 => style.bg(Color::Reset),
+            38 | 48 => {
+                // `38;5;<index>` or `38;2;<r>;<g>;<b>`
+                let color = match codes.next() {
+                    Some(5) => codes.next().map(Color::Indexed),
+                    Some(2) => match (codes.next(), codes.next(), codes.next()) {
+                        (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match (code, color) {
+                    (38, Some(color)) => style.fg(color),
+                    (_, Some(color)) => style.bg(color),
+                    (_, None) => style,
+                }
+            }
+            _ => style,
+        };
+    }
+    style
+}
+
+/// Cut lines down to `width` columns and `height` rows, keeping their styles
+pub fn fit(lines: &[Line<'static>], width: u16, height: u16) -> Vec<Line<'static>> {
+    lines
+        .iter()
+        .take(usize::from(height))
+        .map(|line| {
+            let mut left = usize::from(width);
+            let spans = line
+                .spans
+                .iter()
+                .map_while(|span| {
+                    if left == 0 {
+                        return None;
+                    }
+                    let content: String = span.content.chars().take(left).collect();
+                    left -= content.chars().count();
+                    Some(Span::styled(content, span.style))
+                })
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect()
+}
@@ -1,39 +1,137 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        self, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+    },
 };
 use ratatui::{
-    Terminal,
+    Terminal, TerminalOptions, Viewport,
     backend::{Backend, CrosstermBackend},
+    layout::Rect,
 };
-use std::{error::Error, io};
+use rsysfetch::{
+    AppResult,
+    app::App,
+    cli::Cli,
+    config::{self, Config},
+    fields,
+    health::{EXIT_THRESHOLD_BREACHED, Thresholds},
+    output,
+    panel::Panel,
+    print,
+    system_info::{self, SystemInfo},
+    template,
+    theme::{self, Theme},
+    ui,
+};
+use std::{
+    io::{self, IsTerminal},
+    process, thread,
+};
+
+fn main() -> AppResult<()> {
+    let cli = Cli::parse()?;
+    if cli.help {
+        println!("{}", Cli::usage());
+        return Ok(());
+    }
+    if cli.version {
+        println!("rsysfetch {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    if cli.list_fields {
+        print!("{}", fields::list());
+        return Ok(());
+    }
+    if cli.init_config {
+        let path = config::init_config(cli.force)?;
+        println!("Wrote {}", path.display());
+        return Ok(());
+    }
 
-mod app;
-mod logo;
-mod system_info;
-mod ui;
+    let mut config = Config::load(cli.profile.as_deref())?;
+    if cli.check_config {
+        let mut overrides = Config::default();
+        cli.apply_to(&mut overrides);
+        let mut settings = config::resolved_settings(&config, &overrides)?;
+        let (_, mut invalid) = Theme::from_config(&config.theme);
+        invalid.extend(theme::item_colors(&config.colors).1);
+        invalid.extend(Panel::hardware_order(config.sections.as_deref().unwrap_or_default()).1);
+        if !invalid.is_empty() {
+            return Err(invalid.join(", ").into());
+        }
+        settings.push(match &cli.fields {
+            Some(fields) => (format!("fields = {}", fields.join(",")), "cli"),
+            None => ("fields = all".to_string(), "default"),
+        });
+        print!("{}", config::format_settings(&settings));
+        return Ok(());
+    }
+    cli.apply_to(&mut config);
+    system_info::set_command_timeout(config.command_timeout()?);
 
-use app::App;
+    // Load the fixture before touching the terminal so errors print normally
+    let mock = cli.mock.as_deref().map(SystemInfo::load_mock).transpose()?;
 
-type AppResult<T> = Result<T, Box<dyn Error>>;
+    // Without a terminal to draw on, fall back to printing once
+    let interactive = io::stdout().is_terminal();
+    if cli.format.is_some() || cli.oneline || cli.print || cli.output.is_some() || !interactive {
+        let info = collect_once(mock, &config)?;
+        let breaches = Thresholds::from_config(&config.thresholds).breaches(&info);
+        print_once(&cli, &config, info, interactive)?;
+
+        for breach in &breaches {
+            eprintln!("rsysfetch: {breach}");
+        }
+        if !breaches.is_empty() {
+            process::exit(EXIT_THRESHOLD_BREACHED);
+        }
+        return Ok(());
+    }
 
-fn main() -> AppResult<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let terminal = if cli.inline {
+        // Draw in the normal buffer so recordings capture it and the last frame stays in scrollback
+        terminal::size().and_then(|(_, height)| {
+            Terminal::with_options(
+                CrosstermBackend::new(stdout),
+                TerminalOptions {
+                    viewport: Viewport::Inline(height),
+                },
+            )
+        })
+    } else {
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+            .and_then(|()| Terminal::new(CrosstermBackend::new(stdout)))
+    };
+    // The inline viewport asks the terminal for the cursor position, which can time out
+    let mut terminal = match terminal {
+        Ok(terminal) => terminal,
+        Err(err) => {
+            disable_raw_mode()?;
+            return Err(err.into());
+        }
+    };
 
-    let app = App::new()?;
+    let app = match mock {
+        Some(info) => App::with_info(&config, info)?,
+        None => App::new(&config)?,
+    };
     let res = run_app(&mut terminal, app);
 
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if cli.inline {
+        println!();
+    } else {
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
+    }
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -43,17 +141,160 @@ fn main() -> AppResult<()> {
     Ok(())
 }
 
+/// Write the info once in the requested format, template or text layout
+fn print_once(cli: &Cli, config: &Config, info: SystemInfo, interactive: bool) -> AppResult<()> {
+    if let Some(format) = cli.format {
+        let text = output::render(&info, format, cli.fields.as_deref())?;
+        return match &cli.output {
+            Some(path) => output::write(path, &text),
+            None => {
+                print!("{text}");
+                Ok(())
+            }
+        };
+    }
+
+    if cli.oneline {
+        let line = config
+            .oneline
+            .as_deref()
+            .unwrap_or(template::DEFAULT_ONELINE);
+        let primary_disk = config
+            .primary_disk
+            .as_deref()
+            .unwrap_or(config::default_primary_disk());
+        println!(
+            "{}",
+            template::render(
+                line,
+                &info,
+                config.title_unknown.unwrap_or_default(),
+                primary_disk
+            )
+        );
+        return Ok(());
+    }
+
+    let app = App::with_info(config, info)?;
+    match &cli.output {
+        Some(path) => output::write(path, &print::render(&app, false))?,
+        None => print!("{}", print::render(&app, interactive && !app.no_color)),
+    }
+    Ok(())
+}
+
+/// The fixture, or a fresh sample for output that is printed once
+fn collect_once(mock: Option<SystemInfo>, config: &Config) -> AppResult<SystemInfo> {
+    if let Some(info) = mock {
+        return Ok(info);
+    }
+    // The lookup is the slowest part; let it run while the rest is sampled
+    let public_ip = config
+        .public_ip
+        .unwrap_or(false)
+        .then(|| thread::spawn(SystemInfo::lookup_public_ip));
+    let mut info = SystemInfo::collect()?;
+    if let Some(lookup) = public_ip {
+        info.set_public_ip(
+            lookup
+                .join()
+                .unwrap_or_else(|_| Err("lookup panicked".to_string())),
+        );
+    }
+    Ok(info)
+}
+
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> AppResult<()> {
     loop {
         terminal.draw(|f| ui::draw(f, &app))?;
+        app.refreshed_manually = false;
+
+        // Block on input when auto-refresh is off or paused, otherwise refresh on every tick.
+        // Waiting only for the rest of the interval keeps key presses from postponing it.
+        if !app.refresh_on_keypress_only && !app.paused {
+            let timeout = app.interval.saturating_sub(app.last_refresh.elapsed());
+            if !event::poll(timeout)? {
+                app.refresh();
+                continue;
+            }
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            Event::Resize(width, height) => {
+                // Clamping also takes content that fits again back to the top
+                let (max_scroll, _) = ui::scroll_range(&app, Rect::new(0, 0, width, height));
+                app.scroll_by(0, max_scroll);
+                continue;
+            }
+            _ => continue,
+        };
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => {
-                    app.should_quit = true;
-                }
-                _ => {}
+        let (max_scroll, page) = ui::scroll_range(&app, terminal.size()?);
+        match key.code {
+            KeyCode::Char('?') => {
+                app.show_help = !app.show_help;
+            }
+            // Esc closes the help popup first, and only quits once it is gone
+            KeyCode::Esc if app.show_help => {
+                app.show_help = false;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.should_quit = true;
+            }
+            KeyCode::Char('r') => {
+                app.refresh_now();
+            }
+            KeyCode::Char('f') => {
+                app.paused = !app.paused;
+            }
+            KeyCode::Char('p') => {
+                app.toggle_process_sort();
+            }
+            // `=` shares the key with `+` on most layouts
+            KeyCode::Char('+' | '=') => {
+                app.adjust_interval(true);
+            }
+            KeyCode::Char('-') => {
+                app.adjust_interval(false);
+            }
+            KeyCode::Char('s') => {
+                app.save_report();
+            }
+            KeyCode::Char('u') => {
+                app.byte_unit = app.byte_unit.toggled();
+            }
+            KeyCode::Char('t') => {
+                app.cycle_theme();
+            }
+            KeyCode::Char('d') => {
+                app.show_details = !app.show_details;
+            }
+            KeyCode::Tab => {
+                app.focus_next();
+            }
+            KeyCode::BackTab => {
+                app.focus_previous();
+            }
+            KeyCode::Char(digit @ '1'..='7') => {
+                app.toggle_hidden(usize::from(digit as u8 - b'1'));
+            }
+            KeyCode::Char(' ') => {
+                app.toggle_collapsed();
+            }
+            KeyCode::Up => {
+                app.scroll_by(-1, max_scroll);
+            }
+            KeyCode::Down => {
+                app.scroll_by(1, max_scroll);
+            }
+            KeyCode::PageUp => {
+                app.scroll_by(-i32::from(page), max_scroll);
+            }
+            KeyCode::PageDown => {
+                app.scroll_by(i32::from(page), max_scroll);
             }
+            _ => {}
         }
 
         if app.should_quit {
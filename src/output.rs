@@ -0,0 +1,181 @@
+use crate::AppResult;
+use crate::system_info::SystemInfo;
+use chrono::Local;
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Machine-readable formats for non-interactive output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Yaml,
+    Json,
+    Env,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "yaml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            "env" => Ok(Self::Env),
+            other => Err(format!(
+                "invalid format '{other}' (expected yaml, json or env)"
+            )),
+        }
+    }
+}
+
+/// Serialize the system information in the given format, keeping only `fields` when given
+pub fn render(
+    info: &SystemInfo,
+    format: OutputFormat,
+    fields: Option<&[String]>,
+) -> AppResult<String> {
+    let mut value = serde_yaml::to_value(info)?;
+    if let (Some(fields), Value::Mapping(mapping)) = (fields, &mut value) {
+        mapping.retain(|key, _| {
+            key.as_str()
+                .is_some_and(|key| fields.iter().any(|field| field == key))
+        });
+    }
+
+    match format {
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(&value)?),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&value)? + "\n"),
+        OutputFormat::Env => {
+            let mut lines = Vec::new();
+            flatten_env("RSYSFETCH", &value, &mut lines);
+            Ok(lines.into_iter().map(|line| line + "\n").collect())
+        }
+    }
+}
+
+/// Flatten a value into `PREFIX_KEY=value` lines; lists get `_<index>` keys and a `_COUNT`
+fn flatten_env(prefix: &str, value: &Value, lines: &mut Vec<String>) {
+    match value {
+        // Unknown values are left unset
+        Value::Null => {}
+        Value::Bool(value) => lines.push(format!("{prefix}={value}")),
+        Value::Number(value) => lines.push(format!("{prefix}={value}")),
+        Value::String(value) => lines.push(format!("{prefix}={}", shell_quote(value))),
+        Value::Sequence(items) => {
+            lines.push(format!("{prefix}_COUNT={}", items.len()));
+            for (index, item) in items.iter().enumerate() {
+                flatten_env(&format!("{prefix}_{index}"), item, lines);
+            }
+        }
+        Value::Mapping(fields) => {
+            for (key, item) in fields {
+                if let Some(key) = key.as_str() {
+                    flatten_env(&format!("{prefix}_{}", key.to_uppercase()), item, lines);
+                }
+            }
+        }
+        Value::Tagged(tagged) => flatten_env(prefix, &tagged.value, lines),
+    }
+}
+
+/// Quote a string for POSIX shells: single quotes, with embedded ones written as `'\''`
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Write rendered output to `path`, naming the file in the error
+pub fn write(path: &Path, text: &str) -> AppResult<()> {
+    fs::write(path, text).map_err(|err| format!("could not write {}: {err}", path.display()).into())
+}
+
+/// `rsysfetch-YYYYMMDD-HHMMSS.txt` in the current directory, stamped with the local time
+pub fn report_path() -> PathBuf {
+    PathBuf::from(format!(
+        "rsysfetch-{}.txt",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo() -> SystemInfo {
+        SystemInfo::load_mock(Path::new("fixtures/demo.json")).unwrap()
+    }
+
+    #[test]
+    fn yaml_round_trips() {
+        let info = demo();
+        let yaml = render(&info, OutputFormat::Yaml, None).unwrap();
+        let parsed: SystemInfo = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&info).unwrap()
+        );
+    }
+
+    /// Read `KEY=value` lines the way a POSIX shell would after `eval`:
+    /// quoted text is literal, a backslash escapes the next character
+    fn parse_env(text: &str) -> Vec<(String, String)> {
+        text.lines()
+            .map(|line| {
+                let (key, raw) = line.split_once('=').unwrap();
+                let mut value = String::new();
+                let mut chars = raw.chars();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' => value.extend(chars.by_ref().take_while(|c| *c != '\'')),
+                        '\\' => value.extend(chars.next()),
+                        c => value.push(c),
+                    }
+                }
+                (key.to_string(), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn env_output_reads_back_through_shell_quoting() {
+        let mut info = demo();
+        let tricky = r#"GeForce "RTX" 4070's $HOME `id` \n"#;
+        info.gpus[0].name = tricky.to_string();
+        info.host_model = Some("Model 'X' with  spaces".to_string());
+
+        let env = render(&info, OutputFormat::Env, None).unwrap();
+        let vars = parse_env(&env);
+        let var = |key: &str| {
+            vars.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+
+        assert!(vars.iter().all(|(key, _)| key.starts_with("RSYSFETCH_")));
+        assert_eq!(var("RSYSFETCH_GPUS_0_NAME"), Some(tricky));
+        assert_eq!(var("RSYSFETCH_HOST_MODEL"), Some("Model 'X' with  spaces"));
+        assert_eq!(var("RSYSFETCH_OS_NAME"), Some("Arch Linux"));
+        assert_eq!(var("RSYSFETCH_CPUS_COUNT"), Some("1"));
+        assert_eq!(var("RSYSFETCH_CPUS_0_CORES"), Some("16"));
+        assert_eq!(var("RSYSFETCH_IS_VIRTUAL"), Some("false"));
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let info = demo();
+        let json = render(&info, OutputFormat::Json, None).unwrap();
+        let parsed: SystemInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&info).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_keeps_raw_numbers() {
+        let json = render(&demo(), OutputFormat::Json, None).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["cpus"][0]["cores"], 16);
+        assert_eq!(value["memory_total"], 33_285_996_544u64);
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/// A bordered section of the full layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Panel {
+    System,
+    Cpu,
+    Memory,
+    Battery,
+    Disk,
+    Gpu,
+    Network,
+    Processes,
+}
+
+impl Panel {
+    /// All panels in the default display order
+    pub const ALL: [Panel; 8] = [
+        Panel::System,
+        Panel::Cpu,
+        Panel::Memory,
+        Panel::Battery,
+        Panel::Disk,
+        Panel::Gpu,
+        Panel::Network,
+        Panel::Processes,
+    ];
+
+    /// Panels shown below the system panel, in the default order
+    pub const HARDWARE: [Panel; 7] = [
+        Panel::Cpu,
+        Panel::Memory,
+        Panel::Battery,
+        Panel::Disk,
+        Panel::Gpu,
+        Panel::Network,
+        Panel::Processes,
+    ];
+
+    /// Name used in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Panel::System => "system",
+            Panel::Cpu => "cpu",
+            Panel::Memory => "memory",
+            Panel::Battery => "battery",
+            Panel::Disk => "disk",
+            Panel::Gpu => "gpu",
+            Panel::Network => "network",
+            Panel::Processes => "processes",
+        }
+    }
+
+    /// Order the hardware panels by the `sections` names; panels left out follow in their
+    /// default order. Unknown and repeated names are skipped and reported.
+    pub fn hardware_order(names: &[String]) -> (Vec<Panel>, Vec<String>) {
+        let mut order = Vec::new();
+        let mut invalid = Vec::new();
+
+        for name in names {
+            match Self::HARDWARE
+                .into_iter()
+                .find(|panel| panel.name() == name)
+            {
+                Some(panel) if !order.contains(&panel) => order.push(panel),
+                Some(_) => invalid.push(format!("repeated section '{name}' in sections")),
+                None => invalid.push(format!("unknown section '{name}' in sections")),
+            }
+        }
+        for panel in Self::HARDWARE {
+            if !order.contains(&panel) {
+                order.push(panel);
+            }
+        }
+
+        (order, invalid)
+    }
+}
+
+/// A line of the info panels that can be given its own color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InfoItem {
+    Os,
+    Kernel,
+    Host,
+    User,
+    Security,
+    Uptime,
+    Power,
+    Packages,
+    Shell,
+    Terminal,
+    Cpu,
+    Memory,
+    Disk,
+    Gpu,
+    Displays,
+    Audio,
+    Network,
+}
+
+impl InfoItem {
+    /// Key used in the `[colors]` table of the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            InfoItem::Os => "os",
+            InfoItem::Kernel => "kernel",
+            InfoItem::Host => "host",
+            InfoItem::User => "user",
+            InfoItem::Security => "security",
+            InfoItem::Uptime => "uptime",
+            InfoItem::Power => "power",
+            InfoItem::Packages => "packages",
+            InfoItem::Shell => "shell",
+            InfoItem::Terminal => "terminal",
+            InfoItem::Cpu => "cpu",
+            InfoItem::Memory => "memory",
+            InfoItem::Disk => "disk",
+            InfoItem::Gpu => "gpu",
+            InfoItem::Displays => "displays",
+            InfoItem::Audio => "audio",
+            InfoItem::Network => "network",
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::app::App;
+use crate::logo;
+use crate::ui;
+use crossterm::style::{Attribute, ContentStyle};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+
+/// Width of the info column; usage bars are sized to fit it
+const INFO_WIDTH: u16 = 60;
+
+/// Size of the braille logo drawn from `logo_image`, and the most a `logo` file may take
+const IMAGE_WIDTH: u16 = 40;
+const IMAGE_HEIGHT: u16 = 20;
+
+/// Spaces between the logo and the info column
+const GAP: usize = 2;
+
+/// Render the logo and the info lines side by side as plain text, styled with ANSI
+/// escapes when `color` is set
+pub fn render(app: &App, color: bool) -> String {
+    let logo = match &app.logo_image {
+        Some(image) => logo::braille_logo(
+            image,
+            IMAGE_WIDTH,
+            IMAGE_HEIGHT,
+            app.logo_image_color,
+            &app.theme,
+        ),
+        None => match &app.logo_text {
+            Some(lines) => logo::fit(lines, IMAGE_WIDTH, IMAGE_HEIGHT),
+            None => logo::get_logo_for(app.system_info.os_name.as_deref(), &app.theme, app.glyphs),
+        },
+    };
+    let info = ui::summary_lines(app, INFO_WIDTH);
+
+    let logo_width = logo.iter().map(Line::width).max().unwrap_or(0);
+    let mut output = String::new();
+    for row in 0..logo.len().max(info.len()) {
+        let mut text = String::new();
+        let mut width = 0;
+        if let Some(line) = logo.get(row) {
+            text.push_str(&line_text(line, color));
+            width = line.width();
+        }
+        if let Some(line) = info.get(row) {
+            text.push_str(&" ".repeat(logo_width - width + GAP));
+            text.push_str(&line_text(line, color));
+        }
+        output.push_str(text.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+fn line_text(line: &Line, color: bool) -> String {
+    line.spans
+        .iter()
+        .map(|span| {
+            if color {
+                ansi_style(span.style).apply(&span.content).to_string()
+            } else {
+                span.content.to_string()
+            }
+        })
+        .collect()
+}
+
+/// The foreground color and modifiers of a ratatui style, as a crossterm style
+fn ansi_style(style: Style) -> ContentStyle {
+    let mut ansi = ContentStyle::new();
+    ansi.foreground_color = style.fg.filter(|fg| *fg != Color::Reset).map(Into::into);
+
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::REVERSED, Attribute::Reverse),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            ansi.attributes.set(attribute);
+        }
+    }
+    ansi
+}
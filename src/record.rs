@@ -0,0 +1,126 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Only rewrite the state file once the record grew by at least this much,
+/// so a running session does not write to disk on every refresh
+const WRITE_STEP: u64 = 60;
+
+/// Longest uptime ever observed on this machine, persisted in a small state file
+#[derive(Debug, Clone)]
+pub struct UptimeRecord {
+    path: Option<PathBuf>,
+    stored: Option<u64>,
+    pub seconds: u64,
+}
+
+impl UptimeRecord {
+    /// Load the stored record (if any) from the default state path
+    pub fn load() -> Self {
+        let path = state_path();
+        let stored = path.as_deref().and_then(read_record);
+
+        Self {
+            path,
+            stored,
+            seconds: stored.unwrap_or(0),
+        }
+    }
+
+    /// A record that starts empty and is never persisted, for mock data
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            stored: None,
+            seconds: 0,
+        }
+    }
+
+    /// Record the current uptime, persisting it when it beats the stored value
+    pub fn observe(&mut self, uptime: u64) {
+        self.seconds = self.seconds.max(uptime);
+
+        let Some(new_record) = updated_record(self.stored, uptime) else {
+            return;
+        };
+        if let Some(path) = &self.path {
+            if write_record(path, new_record).is_ok() {
+                self.stored = Some(new_record);
+            }
+        }
+    }
+}
+
+/// Decide whether `current` should replace the stored record
+pub fn updated_record(stored: Option<u64>, current: u64) -> Option<u64> {
+    match stored {
+        Some(stored) if current < stored.saturating_add(WRITE_STEP) => None,
+        _ => Some(current),
+    }
+}
+
+/// Get the state file path (`~/.local/state/rsysfetch/record` on Unix)
+pub fn state_path() -> Option<PathBuf> {
+    let dir = if cfg!(target_os = "windows") {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+            })
+    };
+
+    dir.map(|dir| dir.join("rsysfetch").join("record"))
+}
+
+fn read_record(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Write the record atomically: a crash mid-write never leaves a truncated file
+fn write_record(path: &Path, seconds: u64) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, format!("{seconds}\n"))?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_uptime_becomes_the_record() {
+        assert_eq!(updated_record(None, 3600), Some(3600));
+    }
+
+    #[test]
+    fn record_is_kept_until_beaten_by_a_full_step() {
+        assert_eq!(updated_record(Some(3600), 1200), None);
+        assert_eq!(updated_record(Some(3600), 3600), None);
+        assert_eq!(updated_record(Some(3600), 3600 + WRITE_STEP - 1), None);
+        assert_eq!(
+            updated_record(Some(3600), 3600 + WRITE_STEP),
+            Some(3600 + WRITE_STEP)
+        );
+    }
+
+    #[test]
+    fn corrupt_record_file_is_replaced() {
+        let path = env::temp_dir().join(format!("rsysfetch-record-{}", std::process::id()));
+        fs::write(&path, "not a number\n").unwrap();
+
+        let stored = read_record(&path);
+        assert_eq!(stored, None);
+        assert_eq!(updated_record(stored, 120), Some(120));
+
+        write_record(&path, 120).unwrap();
+        assert_eq!(read_record(&path), Some(120));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,341 +1,2566 @@
 use crate::AppResult;
-use local_ip_address::local_ip;
+use crate::config::{ByteUnit, TempUnit, UptimeFormat};
+use chrono::{Local, TimeZone};
+use local_ip_address::{list_afinet_netifas, local_ip};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::env;
-use std::process::Command;
-use sysinfo::System;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{
+    Components, CpuRefreshKind, Disks, MINIMUM_CPU_UPDATE_INTERVAL, MemoryRefreshKind, Networks,
+    Pid, RefreshKind, System,
+};
+
+/// Whether this build targets one of the BSDs
+const IS_BSD: bool = cfg!(any(
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly"
+));
 
 /// CPU information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuInfo {
     pub model: String,
+    /// Vendor ID reported by the CPU, e.g. `GenuineIntel` or `AuthenticAMD`
+    pub vendor: Option<String>,
     pub cores: usize,
     pub frequency: u64, // MHz
 }
 
-/// GPU information structure
-#[derive(Debug, Clone)]
-pub struct GpuInfo {
-    pub name: String,
-    pub vendor: String,
+/// GPU information structure
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub name: String,
+    pub vendor: Option<String>,
+    pub driver_version: Option<String>,
+    pub temperature: Option<f32>, // °C
+    pub vram_used: Option<u64>,   // bytes; only reported for NVIDIA cards
+    pub vram_total: Option<u64>,  // bytes
+}
+
+/// Network interface structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetInterface {
+    pub name: String,
+    pub rx_rate: u64,  // bytes per second
+    pub tx_rate: u64,  // bytes per second
+    pub rx_total: u64, // bytes since boot
+    pub tx_total: u64, // bytes since boot
+    pub ipv4: Option<String>,
+    pub ipv6: Option<String>,
+    pub mac: Option<String>,
+}
+
+/// Connected display structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayInfo {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool,
+}
+
+/// Mounted disk structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub mount_point: String,
+    pub fs_type: String,
+    pub total: u64, // bytes
+    pub used: u64,  // bytes
+    pub inodes_total: Option<u64>,
+    pub inodes_used: Option<u64>,
+}
+
+/// Running process, as listed in the Processes panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub name: String,
+    pub pid: u32,
+    pub cpu: f32,    // percent of one core
+    pub memory: u64, // bytes, resident
+}
+
+/// What the process list is ordered by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessSort {
+    #[default]
+    Memory,
+    Cpu,
+}
+
+impl ProcessSort {
+    /// The other order, for the `p` key
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Memory => Self::Cpu,
+            Self::Cpu => Self::Memory,
+        }
+    }
+
+    /// Order `processes` from the biggest user down
+    pub fn sort(self, processes: &mut [ProcessInfo]) {
+        match self {
+            Self::Memory => processes.sort_by_key(|process| Reverse(process.memory)),
+            Self::Cpu => processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu)),
+        }
+    }
+}
+
+/// Number of processes kept in `top_processes`
+pub const TOP_PROCESSES: usize = 5;
+
+/// Laptop battery charge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    pub percent: f32,
+    /// `Charging`, `Discharging`, `Full`, `Not charging` or `Unknown`
+    pub state: String,
+    pub time_remaining: Option<u64>, // seconds until empty or full
+}
+
+/// Resource limits of the cgroup the process runs in, when lower than the host's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CgroupLimits {
+    pub memory: Option<u64>, // bytes
+    pub cpus: Option<f64>,   // CPUs worth of quota
+}
+
+/// macOS memory pressure level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemPressure {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Platform security features
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityInfo {
+    pub secure_boot: Option<bool>,
+    pub tpm: Option<String>, // e.g. "TPM 2.0"
+}
+
+/// System information structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemInfo {
+    pub os_name: Option<String>,
+    pub os_version: Option<String>,
+    pub os_arch: String,
+    pub kernel_version: Option<String>,
+    pub kernel_build: Option<String>,
+    pub kernel_modules: Option<usize>,
+    pub hostname: Option<String>,
+    pub host_model: Option<String>, // e.g. "ThinkPad X1 Carbon Gen 9", "MacBookPro18,3"
+    /// Running as a virtual machine guest, even when the hypervisor can't be named
+    pub is_virtual: bool,
+    pub hypervisor: Option<String>, // e.g. "KVM", "VMware"
+    pub username: Option<String>,
+    pub security: Option<SecurityInfo>,
+    pub uptime_seconds: u64,
+    pub boot_time: Option<String>, // local time, `2024-06-01 08:32`
+    pub load_average: Option<(f64, f64, f64)>, // 1, 5 and 15 minutes
+    pub cpus: Vec<CpuInfo>,
+    pub cpu_usage: f32,        // percent, all cores
+    pub cpu_frequency: u64,    // MHz, current clock of the first core; 0 when not reported
+    pub cpu_temp: Option<f32>, // °C
+    pub core_usage: Vec<f32>,  // percent, per logical core
+    pub cpu_governor: Option<String>,
+    pub power_profile: Option<String>,
+    pub memory_total: u64,
+    pub memory_used: u64,
+    pub memory_pressure: Option<MemPressure>,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    pub cgroup: Option<CgroupLimits>,
+    pub gpus: Vec<GpuInfo>,
+    pub displays: Vec<DisplayInfo>,
+    pub monitor_count: usize,
+    pub audio_device: Option<String>,
+    pub local_ip: Option<String>,
+    /// Only looked up with `--public-ip`, since it sends a request off the machine
+    pub public_ip: Option<String>,
+    pub interfaces: Vec<NetInterface>,
+    pub disks: Vec<DiskInfo>,
+    pub top_processes: Vec<ProcessInfo>, // biggest first, by the collector's sort order
+    pub on_ac_power: Option<bool>,
+    pub battery: Option<BatteryInfo>,
+    pub lid_open: Option<bool>,
+    pub packages: Vec<(String, usize)>, // (manager, count)
+    pub shell: Option<String>,
+    pub terminal: Option<String>,
+    /// Why probes failed, e.g. `GPU: could not run lspci: ...`
+    pub errors: Vec<String>,
+}
+
+/// Live handles used to sample system information
+#[derive(Debug)]
+pub struct Collector {
+    sys: System,
+    networks: Networks,
+    networks_sampled_at: Instant,
+    network_totals: HashMap<String, (CounterTotal, CounterTotal)>,
+    disks: Disks,
+    components: Components,
+    /// Keep loopback interfaces in the interface list
+    include_loopback: bool,
+    /// Walk the process table on refresh, for `top_processes`
+    include_processes: bool,
+    process_sort: ProcessSort,
+    /// When the probes that spawn a program last ran, see `SLOW_PROBE_INTERVAL`
+    slow_probes_sampled_at: Option<Instant>,
+}
+
+/// How often `refresh_dynamic` re-runs the probes that spawn a program
+/// (nvidia-smi, vm_stat, pmset, wmic); between runs the previous readings are kept
+const SLOW_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+impl Collector {
+    pub fn new() -> Self {
+        Self {
+            // Only the CPU and memory tables; the process table is by far the slowest to read
+            sys: System::new_with_specifics(
+                RefreshKind::new()
+                    .with_cpu(CpuRefreshKind::everything())
+                    .with_memory(MemoryRefreshKind::everything()),
+            ),
+            networks: Networks::new_with_refreshed_list(),
+            networks_sampled_at: Instant::now(),
+            network_totals: HashMap::new(),
+            disks: Disks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            include_loopback: false,
+            include_processes: true,
+            process_sort: ProcessSort::default(),
+            slow_probes_sampled_at: None,
+        }
+    }
+
+    /// Pick which processes `top_processes` keeps
+    pub fn set_process_sort(&mut self, sort: ProcessSort) {
+        self.process_sort = sort;
+    }
+
+    /// Skip reading the process table when `top_processes` isn't shown; it is left empty
+    pub fn set_include_processes(&mut self, include: bool) {
+        self.include_processes = include;
+    }
+
+    /// List loopback interfaces along with the others (`--all-interfaces`)
+    pub fn set_include_loopback(&mut self, include: bool) {
+        self.include_loopback = include;
+    }
+
+    /// Whether the slow probes are due, marking them sampled if so
+    fn slow_probes_due(&mut self, now: Instant) -> bool {
+        let due = self
+            .slow_probes_sampled_at
+            .is_none_or(|at| now.duration_since(at) >= SLOW_PROBE_INTERVAL);
+        if due {
+            self.slow_probes_sampled_at = Some(now);
+        }
+        due
+    }
+}
+
+/// Cumulative counter that survives the source counter being reset
+#[derive(Debug, Clone, Copy, Default)]
+struct CounterTotal {
+    base: u64,
+    last: u64,
+}
+
+impl CounterTotal {
+    /// Record a new reading and return the running total
+    fn observe(&mut self, value: u64) -> u64 {
+        // A counter going backwards was reset (e.g. a VPN interface went down and up)
+        if value < self.last {
+            self.base += self.last;
+        }
+        self.last = value;
+        self.base + value
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemInfo {
+    /// Collect system information, waiting long enough for a meaningful CPU usage
+    pub fn collect() -> AppResult<Self> {
+        Self::collect_sampled(MINIMUM_CPU_UPDATE_INTERVAL)
+    }
+
+    /// Collect system information with CPU usage measured over at least `cpu_sample`.
+    ///
+    /// The static probes count towards the wait; `Duration::ZERO` returns right away,
+    /// with CPU usage reading as 0.
+    pub fn collect_sampled(cpu_sample: Duration) -> AppResult<Self> {
+        let started = Instant::now();
+        let mut collector = Collector::new();
+        let mut info = Self::collect_static(&collector);
+
+        if let Some(remaining) = cpu_sample.checked_sub(started.elapsed()) {
+            thread::sleep(remaining);
+        }
+        info.refresh_dynamic(&mut collector);
+
+        Ok(info)
+    }
+
+    /// Load a previously serialized `SystemInfo` from a JSON fixture
+    pub fn load_mock(path: &Path) -> AppResult<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("could not read mock {}: {err}", path.display()))?;
+        serde_json::from_str(&content)
+            .map_err(|err| format!("invalid mock {}: {err}", path.display()).into())
+    }
+
+    /// Collect the values that do not change while the program runs.
+    /// Dynamic values are left empty until `refresh_dynamic` is called.
+    pub fn collect_static(collector: &Collector) -> Self {
+        let sys = &collector.sys;
+
+        // The probes that shell out are independent of each other; run them side by side.
+        // One that panics reads as unknown instead of taking the rest down with it.
+        thread::scope(|scope| {
+            let kernel_build = scope.spawn(get_kernel_build);
+            let host_model = scope.spawn(get_host_model);
+            let virtualization = scope.spawn(get_virtualization);
+            let security = scope.spawn(get_security_info);
+            let power_profile = scope.spawn(get_power_profile);
+            let gpus = scope.spawn(get_gpu_info_list);
+            let displays = scope.spawn(get_display_info_list);
+            let audio_device = scope.spawn(get_audio_device);
+            let packages = scope.spawn(get_package_counts);
+            let shell = scope.spawn(get_shell_info);
+
+            // Basic system information
+            let os_name = System::name();
+            let os_version = System::os_version();
+            let os_arch = std::env::consts::ARCH.into();
+            let kernel_version = System::kernel_version();
+            let kernel_modules = get_kernel_modules();
+            let hostname = System::host_name().filter(|name| !name.is_empty());
+            let username = env::var("USER")
+                .or_else(|_| env::var("USERNAME"))
+                .ok()
+                .filter(|name| !name.is_empty());
+            let boot_time = format_boot_time(System::boot_time());
+
+            // CPU information
+            let cpus = collect_cpu_info(sys);
+            let cpu_governor = get_cpu_governor();
+
+            // Memory information
+            let memory_total = sys.total_memory();
+            let cgroup = get_cgroup_limits(memory_total, sys.cpus().len());
+
+            // Probes that could not run at all, so their fields read as unknown
+            let mut errors = Vec::new();
+
+            // Local IP address
+            let local_ip = match get_local_ip() {
+                Ok(ip) => Some(ip),
+                Err(err) => {
+                    errors.push(format!("local IP: {err}"));
+                    None
+                }
+            };
+
+            let terminal = detect_terminal();
+
+            // GPU information
+            let gpus = gpus
+                .join()
+                .unwrap_or_else(|_| Err("probe panicked".to_string()))
+                .unwrap_or_else(|err| {
+                    errors.push(format!("GPU: {err}"));
+                    Vec::new()
+                });
+            let displays = displays.join().unwrap_or_default();
+            let monitor_count = displays.len();
+            let (is_virtual, hypervisor) = virtualization.join().unwrap_or_default();
+
+            Self {
+                os_name,
+                os_version,
+                os_arch,
+                kernel_version,
+                kernel_build: kernel_build.join().unwrap_or_default(),
+                kernel_modules,
+                hostname,
+                host_model: host_model.join().unwrap_or_default(),
+                is_virtual,
+                hypervisor,
+                username,
+                security: security.join().unwrap_or_default(),
+                uptime_seconds: 0,
+                boot_time,
+                load_average: None,
+                cpus,
+                cpu_usage: 0.0,
+                cpu_frequency: 0,
+                cpu_temp: None,
+                core_usage: Vec::new(),
+                cpu_governor,
+                power_profile: power_profile.join().unwrap_or_default(),
+                memory_total,
+                memory_used: 0,
+                memory_pressure: None,
+                swap_total: 0,
+                swap_used: 0,
+                cgroup,
+                gpus,
+                displays,
+                monitor_count,
+                audio_device: audio_device.join().unwrap_or_default(),
+                local_ip,
+                public_ip: None,
+                interfaces: Vec::new(),
+                disks: Vec::new(),
+                top_processes: Vec::new(),
+                on_ac_power: None,
+                battery: None,
+                lid_open: None,
+                packages: packages.join().unwrap_or_default(),
+                shell: shell.join().unwrap_or_default(),
+                terminal,
+                errors,
+            }
+        })
+    }
+
+    /// Look up the public IP address, blocking for up to a few seconds
+    pub fn lookup_public_ip() -> Result<String, String> {
+        get_public_ip()
+    }
+
+    /// Store the result of `lookup_public_ip`, noting why it failed
+    pub fn set_public_ip(&mut self, lookup: Result<String, String>) {
+        match lookup {
+            Ok(ip) => self.public_ip = Some(ip),
+            Err(err) => {
+                self.public_ip = None;
+                self.errors.push(format!("public IP: {err}"));
+            }
+        }
+    }
+
+    /// Number of fields that could not be determined on this machine
+    pub fn unknown_count(&self) -> usize {
+        let fields = [
+            &self.os_name,
+            &self.os_version,
+            &self.kernel_version,
+            &self.hostname,
+            &self.username,
+            &self.audio_device,
+            &self.local_ip,
+            &self.shell,
+            &self.terminal,
+        ];
+
+        fields.iter().filter(|field| field.is_none()).count() + usize::from(self.gpus.is_empty())
+    }
+
+    /// Re-sample the values that change over time (memory, core load and clock, uptime, network rates,
+    /// disk usage, top processes, temperatures, power source)
+    pub fn refresh_dynamic(&mut self, collector: &mut Collector) {
+        let sys = &mut collector.sys;
+        sys.refresh_memory();
+        sys.refresh_cpu_usage();
+        sys.refresh_cpu_frequency();
+        if collector.include_processes {
+            sys.refresh_processes();
+        }
+
+        self.uptime_seconds = System::uptime();
+        // Windows has no load average; sysinfo reports zeros there
+        self.load_average = (!cfg!(windows)).then(|| {
+            let load = System::load_average();
+            (load.one, load.five, load.fifteen)
+        });
+        self.memory_total = sys.total_memory();
+        self.memory_used = sys.used_memory();
+        self.swap_total = sys.total_swap();
+        self.swap_used = sys.used_swap();
+        self.cpu_usage = sys.global_cpu_info().cpu_usage();
+        self.core_usage = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        self.cpu_frequency = sys.cpus().first().map_or(0, |cpu| cpu.frequency());
+
+        // Inside a memory-limited cgroup, report usage against the limit
+        if let Some(limit) = self.cgroup.as_ref().and_then(|cgroup| cgroup.memory) {
+            self.memory_total = limit;
+            self.memory_used = get_cgroup_memory_used().unwrap_or(self.memory_used);
+        }
+
+        self.interfaces = collect_interfaces(collector);
+        self.disks = collect_disks(collector);
+        self.top_processes = if collector.include_processes {
+            collect_top_processes(&collector.sys, collector.process_sort)
+        } else {
+            Vec::new()
+        };
+        self.cpu_temp = get_cpu_temp(collector);
+        self.lid_open = get_lid_open();
+
+        // These spawn a program each time, too slow to run on every tick of the TUI
+        if collector.slow_probes_due(Instant::now()) {
+            self.memory_pressure = get_memory_pressure();
+            refresh_gpu_sensors(&mut self.gpus);
+            self.on_ac_power = get_ac_power();
+            self.battery = get_battery_info();
+        }
+    }
+}
+
+/// External probes, exposed so they can be benchmarked in isolation
+#[doc(hidden)]
+pub mod probes {
+    use super::{DisplayInfo, GpuInfo, SecurityInfo};
+
+    pub fn kernel_build() -> Option<String> {
+        super::get_kernel_build()
+    }
+
+    pub fn security() -> Option<SecurityInfo> {
+        super::get_security_info()
+    }
+
+    pub fn virtualization() -> (bool, Option<String>) {
+        super::get_virtualization()
+    }
+
+    pub fn gpus() -> Vec<GpuInfo> {
+        super::get_gpu_info_list().unwrap_or_default()
+    }
+
+    pub fn displays() -> Vec<DisplayInfo> {
+        super::get_display_info_list()
+    }
+
+    pub fn audio_device() -> Option<String> {
+        super::get_audio_device()
+    }
+
+    pub fn power_profile() -> Option<String> {
+        super::get_power_profile()
+    }
+
+    pub fn local_ip() -> Option<String> {
+        super::get_local_ip().ok()
+    }
+
+    pub fn shell() -> Option<String> {
+        super::get_shell_info()
+    }
+
+    pub fn terminal() -> Option<String> {
+        super::detect_terminal()
+    }
+}
+
+/// Format a byte count using binary (1024) steps
+pub fn format_bytes(bytes: u64) -> String {
+    format_bytes_with(bytes, ByteUnit::Binary)
+}
+
+/// Format a byte count in KiB/MiB/... (1024) or KB/MB/... (1000) steps
+pub fn format_bytes_with(bytes: u64, unit: ByteUnit) -> String {
+    let (step, units) = match unit {
+        ByteUnit::Binary => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB"]),
+        ByteUnit::Decimal => (1000.0, ["B", "KB", "MB", "GB", "TB"]),
+    };
+
+    let mut value = bytes as f64;
+    let mut index = 0;
+    while value >= step && index < units.len() - 1 {
+        value /= step;
+        index += 1;
+    }
+
+    if index == 0 {
+        format!("{} {}", bytes, units[0])
+    } else {
+        format!("{:.1} {}", value, units[index])
+    }
+}
+
+/// Format uptime
+pub fn format_uptime(seconds: u64) -> String {
+    format_uptime_with(seconds, UptimeFormat::Compact)
+}
+
+/// Format a duration as `3d 4h 5m` (`45s` under a minute) or `3 days, 4 hours, 5 minutes`
+pub fn format_uptime_with(seconds: u64, format: UptimeFormat) -> String {
+    let days = seconds / 86400;
+    let hours = (seconds % 86400) / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    match format {
+        UptimeFormat::Compact => {
+            if days > 0 {
+                format!("{}d {}h {}m", days, hours, minutes)
+            } else if hours > 0 {
+                format!("{}h {}m", hours, minutes)
+            } else if minutes > 0 {
+                format!("{}m", minutes)
+            } else {
+                format!("{}s", seconds)
+            }
+        }
+        UptimeFormat::Verbose => {
+            if seconds < 60 {
+                return plural(seconds, "second");
+            }
+            // Zero units are left out: "2 hours, 5 minutes" rather than "0 days, 2 hours, ..."
+            [(days, "day"), (hours, "hour"), (minutes, "minute")]
+                .into_iter()
+                .filter(|(count, _)| *count > 0)
+                .map(|(count, unit)| plural(count, unit))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    }
+}
+
+/// `1 day`, `2 days`
+fn plural(count: u64, unit: &str) -> String {
+    if count == 1 {
+        format!("{count} {unit}")
+    } else {
+        format!("{count} {unit}s")
+    }
+}
+
+/// The boot time as a local date and time; unknown when the platform reports 0
+fn format_boot_time(timestamp: u64) -> Option<String> {
+    if timestamp == 0 {
+        return None;
+    }
+    let time = Local
+        .timestamp_opt(i64::try_from(timestamp).ok()?, 0)
+        .single()?;
+    Some(time.format("%Y-%m-%d %H:%M").to_string())
+}
+
+/// The machine's model or product name
+fn get_host_model() -> Option<String> {
+    let model = if cfg!(target_os = "linux") {
+        fs::read_to_string("/sys/class/dmi/id/product_name").ok()?
+    } else if cfg!(target_os = "macos") {
+        let output = command_output(Command::new("sysctl").args(["-n", "hw.model"])).ok()?;
+        decode_output(&output)
+    } else if cfg!(target_os = "windows") {
+        let output = command_output(Command::new("wmic").args([
+            "computersystem",
+            "get",
+            "model",
+            "/format:value",
+        ]))
+        .ok()?;
+        decode_output(&output)
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Model=").map(str::to_string))?
+    } else {
+        return None;
+    };
+    clean_host_model(&model)
+}
+
+/// Names of the hypervisors `systemd-detect-virt` and FreeBSD's `kern.vm_guest` report by ID
+const HYPERVISOR_IDS: &[(&str, &str)] = &[
+    ("kvm", "KVM"),
+    ("qemu", "QEMU"),
+    ("vmware", "VMware"),
+    ("oracle", "VirtualBox"),
+    ("microsoft", "Hyper-V"),
+    ("hv", "Hyper-V"),
+    ("xen", "Xen"),
+    ("parallels", "Parallels"),
+    ("bhyve", "bhyve"),
+    ("amazon", "AWS Nitro"),
+    ("google", "Google Compute Engine"),
+    ("apple", "Apple Virtualization"),
+    ("bochs", "Bochs"),
+];
+
+/// Substrings of the firmware vendor and product name that give a virtual machine away
+const HYPERVISOR_SIGNATURES: &[(&str, &str)] = &[
+    ("vmware", "VMware"),
+    ("virtualbox", "VirtualBox"),
+    ("innotek", "VirtualBox"),
+    ("kvm", "KVM"),
+    ("qemu", "QEMU"),
+    // Hyper-V guests report `Microsoft Corporation Virtual Machine`
+    ("virtual machine", "Hyper-V"),
+    ("xen", "Xen"),
+    ("parallels", "Parallels"),
+    ("bhyve", "bhyve"),
+    ("bochs", "Bochs"),
+    ("amazon ec2", "AWS Nitro"),
+    ("google compute engine", "Google Compute Engine"),
+];
+
+/// Whether this is a virtual machine guest, and which hypervisor runs it
+fn get_virtualization() -> (bool, Option<String>) {
+    if cfg!(target_os = "linux") {
+        let detected = command_output(Command::new("systemd-detect-virt").arg("--vm"))
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| hypervisor_from_id(&decode_output(&output)));
+        let hypervisor = detected.or_else(|| {
+            let read = |name: &str| fs::read_to_string(format!("/sys/class/dmi/id/{name}"));
+            let firmware = [read("sys_vendor"), read("product_name")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            hypervisor_from_firmware(&firmware)
+        });
+        // x86 guests get the `hypervisor` CPU flag even when nothing names the hypervisor
+        let flagged = fs::read_to_string("/proc/cpuinfo").is_ok_and(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .filter(|line| line.starts_with("flags"))
+                .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"))
+        });
+        (hypervisor.is_some() || flagged, hypervisor)
+    } else if cfg!(target_os = "macos") {
+        let sysctl = |name: &str| {
+            command_output(Command::new("sysctl").args(["-n", name]))
+                .ok()
+                .map(|output| decode_output(&output).trim().to_string())
+        };
+        let hypervisor = sysctl("hw.model").and_then(|model| hypervisor_from_firmware(&model));
+        let flagged = sysctl("kern.hv_vmm_present").as_deref() == Some("1");
+        (hypervisor.is_some() || flagged, hypervisor)
+    } else if cfg!(target_os = "windows") {
+        let hypervisor = command_output(Command::new("wmic").args([
+            "computersystem",
+            "get",
+            "manufacturer,model",
+            "/format:value",
+        ]))
+        .ok()
+        .and_then(|output| hypervisor_from_firmware(&decode_output(&output)));
+        (hypervisor.is_some(), hypervisor)
+    } else if cfg!(target_os = "freebsd") {
+        let hypervisor = command_output(Command::new("sysctl").args(["-n", "kern.vm_guest"]))
+            .ok()
+            .and_then(|output| hypervisor_from_id(&decode_output(&output)));
+        (hypervisor.is_some(), hypervisor)
+    } else {
+        (false, None)
+    }
+}
+
+/// Name the hypervisor behind an ID such as `kvm` or `oracle`; `none` means bare metal
+fn hypervisor_from_id(id: &str) -> Option<String> {
+    let id = id.trim();
+    if id.is_empty() || id == "none" {
+        return None;
+    }
+    let name = HYPERVISOR_IDS
+        .iter()
+        .find(|(known, _)| *known == id)
+        .map_or(id, |(_, name)| name);
+    Some(name.to_string())
+}
+
+/// Recognize a hypervisor from the firmware vendor and product name
+fn hypervisor_from_firmware(firmware: &str) -> Option<String> {
+    let firmware = firmware.to_lowercase();
+    HYPERVISOR_SIGNATURES
+        .iter()
+        .find(|(signature, _)| firmware.contains(signature))
+        .map(|(_, name)| name.to_string())
+}
+
+/// Drop the filler that firmware without a real product name reports
+fn clean_host_model(model: &str) -> Option<String> {
+    const PLACEHOLDERS: [&str; 5] = [
+        "to be filled by o.e.m.",
+        "system product name",
+        "default string",
+        "not applicable",
+        "none",
+    ];
+
+    let model = model.trim();
+    if model.is_empty() || PLACEHOLDERS.contains(&model.to_lowercase().as_str()) {
+        None
+    } else {
+        Some(model.to_string())
+    }
+}
+
+/// Get the full kernel build string (compiler, build date) on Linux
+fn get_kernel_build() -> Option<String> {
+    if cfg!(target_os = "linux") {
+        std::fs::read_to_string("/proc/version")
+            .ok()
+            .and_then(|version| parse_kernel_build(&version))
+    } else if IS_BSD {
+        let output = command_output(Command::new("sysctl").args(["-n", "kern.version"])).ok()?;
+        parse_bsd_kernel_build(&decode_output(&output))
+    } else {
+        None
+    }
+}
+
+/// Parse `sysctl -n kern.version` on the BSDs, dropping the leading
+/// `<system> <release>` part (e.g. `FreeBSD 14.0-RELEASE`)
+fn parse_bsd_kernel_build(kern_version: &str) -> Option<String> {
+    let first_line = kern_version.lines().next()?.trim();
+    let mut parts = first_line.splitn(3, ' ');
+    let build = parts.nth(2)?.trim();
+
+    if build.is_empty() {
+        None
+    } else {
+        Some(build.to_string())
+    }
+}
+
+/// Parse the contents of `/proc/version`, dropping the leading
+/// `Linux version <release>` part that is already shown as the kernel version
+fn parse_kernel_build(proc_version: &str) -> Option<String> {
+    let rest = proc_version.trim().strip_prefix("Linux version ")?;
+    let (_release, build) = rest.split_once(' ')?;
+    let build = build.trim();
+
+    if build.is_empty() {
+        None
+    } else {
+        Some(build.to_string())
+    }
+}
+
+/// Count the loaded kernel modules (Linux only)
+fn get_kernel_modules() -> Option<usize> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    fs::read_to_string("/proc/modules")
+        .ok()
+        .map(|modules| count_kernel_modules(&modules))
+}
+
+/// Count the `name size refcount ...` lines of `/proc/modules`, skipping anything malformed
+fn count_kernel_modules(proc_modules: &str) -> usize {
+    proc_modules
+        .lines()
+        .filter(|line| {
+            let mut fields = line.split_whitespace();
+            fields.next().is_some()
+                && fields
+                    .next()
+                    .is_some_and(|size| size.parse::<u64>().is_ok())
+        })
+        .count()
+}
+
+/// The frequency governor of the first CPU (Linux only)
+fn get_cpu_governor() -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor")
+        .ok()
+        .and_then(|governor| parse_scaling_governor(&governor))
+}
+
+/// The governor name from `scaling_governor`, which holds a single word
+fn parse_scaling_governor(contents: &str) -> Option<String> {
+    let governor = contents.trim();
+    (!governor.is_empty() && !governor.contains(char::is_whitespace)).then(|| governor.to_string())
+}
+
+/// The active power-profiles-daemon profile (Linux only)
+fn get_power_profile() -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let output = command_output(Command::new("powerprofilesctl").arg("get"))
+        .ok()
+        .filter(|output| output.status.success())?;
+    let profile = decode_output(&output).trim().to_string();
+    (!profile.is_empty()).then_some(profile)
+}
+
+/// Decode a command's stdout. `wmic` writes UTF-16LE when its output is piped,
+/// everything else is treated as UTF-8.
+fn decode_output(output: &Output) -> String {
+    decode_bytes(&output.stdout)
+}
+
+fn decode_bytes(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16le(utf16);
+    }
+
+    // Without a BOM, UTF-16LE text is recognizable by the NUL high bytes of ASCII characters
+    let pairs = bytes.len() / 2;
+    let nul_high_bytes = bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    if bytes.len().is_multiple_of(2) && pairs > 0 && nul_high_bytes * 2 > pairs {
+        return decode_utf16le(bytes);
+    }
+
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn decode_utf16le(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Get Secure Boot and TPM state, or `None` when neither can be detected
+fn get_security_info() -> Option<SecurityInfo> {
+    let (secure_boot, tpm) = if cfg!(target_os = "linux") {
+        (
+            read_secure_boot(Path::new("/sys/firmware/efi/efivars")),
+            read_tpm(Path::new("/sys/class/tpm")),
+        )
+    } else if cfg!(target_os = "windows") {
+        (get_secure_boot_windows(), None)
+    } else {
+        (None, None)
+    };
+
+    if secure_boot.is_none() && tpm.is_none() {
+        None
+    } else {
+        Some(SecurityInfo { secure_boot, tpm })
+    }
+}
+
+/// Read the `SecureBoot-<guid>` EFI variable from an efivars directory
+fn read_secure_boot(efivars: &Path) -> Option<bool> {
+    let entry = fs::read_dir(efivars).ok()?.flatten().find(|entry| {
+        entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with("SecureBoot-")
+    })?;
+
+    // The first four bytes hold the variable attributes, the fifth the value
+    let data = fs::read(entry.path()).ok()?;
+    data.get(4).map(|value| *value == 1)
+}
+
+/// Read the TPM version from a `/sys/class/tpm`-style directory
+fn read_tpm(tpm_class: &Path) -> Option<String> {
+    let device = tpm_class.join("tpm0");
+    if !device.exists() {
+        return None;
+    }
+
+    // Only TPM 2.0 devices expose the major version; older ones report it in `caps`
+    if let Ok(major) = fs::read_to_string(device.join("tpm_version_major")) {
+        return Some(format!("TPM {}.0", major.trim()));
+    }
+
+    let caps = fs::read_to_string(device.join("device").join("caps")).unwrap_or_default();
+    let version = caps
+        .lines()
+        .find_map(|line| line.strip_prefix("TCG version:"))
+        .map(str::trim);
+
+    Some(match version {
+        Some(version) => format!("TPM {version}"),
+        None => "TPM".to_string(),
+    })
+}
+
+/// Query Secure Boot state on Windows (requires an elevated shell)
+fn get_secure_boot_windows() -> Option<bool> {
+    let output = command_output(Command::new("powershell").args([
+        "-NoProfile",
+        "-Command",
+        "Confirm-SecureBootUEFI",
+    ]))
+    .ok()?;
+
+    match decode_output(&output).trim() {
+        "True" => Some(true),
+        "False" => Some(false),
+        _ => None,
+    }
+}
+
+/// The `TOP_PROCESSES` processes using the most memory or CPU
+fn collect_top_processes(sys: &System, sort: ProcessSort) -> Vec<ProcessInfo> {
+    let mut processes: Vec<ProcessInfo> = sys
+        .processes()
+        .iter()
+        .map(|(pid, process)| ProcessInfo {
+            name: process.name().to_string(),
+            pid: pid.as_u32(),
+            cpu: process.cpu_usage(),
+            memory: process.memory(),
+        })
+        .collect();
+    sort.sort(&mut processes);
+    processes.truncate(TOP_PROCESSES);
+    processes
+}
+
+/// Collect CPU information
+fn collect_cpu_info(sys: &System) -> Vec<CpuInfo> {
+    let mut cpu_map = std::collections::HashMap::new();
+
+    // Group CPUs by model to handle multi-core processors
+    for cpu in sys.cpus() {
+        let model = cpu.brand().to_string();
+        let frequency = cpu.frequency();
+        let vendor = Some(cpu.vendor_id().trim().to_string()).filter(|vendor| !vendor.is_empty());
+
+        let entry = cpu_map.entry(model.clone()).or_insert(CpuInfo {
+            model,
+            vendor,
+            cores: 0,
+            frequency,
+        });
+        entry.cores += 1;
+    }
+
+    cpu_map.into_values().collect()
+}
+
+/// Get GPU information list
+fn get_gpu_info_list() -> Result<Vec<GpuInfo>, String> {
+    if cfg!(target_os = "windows") {
+        get_gpu_info_windows_list()
+    } else if cfg!(target_os = "linux") {
+        get_gpu_info_linux_list()
+    } else if cfg!(target_os = "macos") {
+        get_gpu_info_macos_list()
+    } else if cfg!(any(target_os = "freebsd", target_os = "dragonfly")) {
+        command_output(Command::new("pciconf").arg("-lv"))
+            .map(|output| parse_pciconf_gpus(&decode_output(&output)))
+            .map_err(|err| format!("could not run pciconf: {err}"))
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Parse the display-class devices out of FreeBSD's `pciconf -lv`.
+///
+/// Each device starts with an unindented `vgapci0@pci0:...` line followed by
+/// indented `vendor = '...'`, `device = '...'` and `class = display` lines.
+fn parse_pciconf_gpus(output: &str) -> Vec<GpuInfo> {
+    let mut gpus = Vec::new();
+    let mut lines = output.lines().peekable();
+
+    while let Some(header) = lines.next() {
+        if header.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        let (mut vendor, mut device, mut is_display) = (None, None, false);
+        while let Some(line) = lines.next_if(|line| line.starts_with(char::is_whitespace)) {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('\'').to_string();
+            match key.trim() {
+                "vendor" => vendor = Some(value),
+                "device" => device = Some(value),
+                "class" => is_display = value == "display",
+                _ => {}
+            }
+        }
+
+        if let (true, Some(name)) = (is_display, device) {
+            gpus.push(GpuInfo {
+                name,
+                vendor,
+                driver_version: None,
+                temperature: None,
+                vram_used: None,
+                vram_total: None,
+            });
+        }
+    }
+
+    gpus
+}
+
+/// Get GPU information on Windows system (multiple GPUs)
+fn get_gpu_info_windows_list() -> Result<Vec<GpuInfo>, String> {
+    let wmic = command_output(Command::new("wmic").args([
+        "path",
+        "win32_VideoController",
+        "get",
+        "name,AdapterCompatibility,DriverVersion",
+        "/format:value",
+    ]))
+    .ok()
+    .filter(|output| output.status.success())
+    .map(|output| parse_video_controllers(&decode_output(&output)))
+    .filter(|gpus| !gpus.is_empty());
+    if let Some(gpus) = wmic {
+        return Ok(gpus);
+    }
+
+    // wmic is gone from recent Windows builds; print the same `Key=value` records from CIM
+    command_output(Command::new("powershell").args([
+        "-NoProfile",
+        "-Command",
+        "Get-CimInstance Win32_VideoController | ForEach-Object { \
+             \"AdapterCompatibility=$($_.AdapterCompatibility)\"; \
+             \"DriverVersion=$($_.DriverVersion)\"; \"Name=$($_.Name)\"; '' }",
+    ]))
+    .map_err(|err| format!("wmic found no GPUs and powershell could not run: {err}"))
+    .and_then(|output| {
+        if output.status.success() {
+            Ok(parse_video_controllers(&decode_output(&output)))
+        } else {
+            Err(format!(
+                "wmic found no GPUs and powershell failed ({})",
+                output.status
+            ))
+        }
+    })
+}
+
+/// Parse `Win32_VideoController` records of `Key=value` lines separated by blank lines,
+/// as printed by `wmic ... /format:value` (with CRLF or CRCRLF line endings)
+fn parse_video_controllers(output: &str) -> Vec<GpuInfo> {
+    let mut gpus = Vec::new();
+    let mut current = GpuInfo::default();
+
+    for line in output.lines().map(str::trim).chain([""]) {
+        let Some((key, value)) = line.split_once('=') else {
+            // A blank line ends the record
+            if !current.name.is_empty() {
+                gpus.push(current);
+            }
+            current = GpuInfo::default();
+            continue;
+        };
+        let value = Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        match key {
+            "AdapterCompatibility" => current.vendor = value,
+            "DriverVersion" => current.driver_version = value,
+            "Name" => current.name = value.unwrap_or_default(),
+            _ => {}
+        }
+    }
+
+    gpus
+}
+
+/// Get GPU information on Linux system (multiple GPUs): the DRM cards in sysfs,
+/// falling back to `lspci`, which minimal containers often lack
+fn get_gpu_info_linux_list() -> Result<Vec<GpuInfo>, String> {
+    let gpus = get_gpu_info_drm();
+    if !gpus.is_empty() {
+        return Ok(gpus);
+    }
+
+    let output = command_output(Command::new("lspci").args(["-mm"]))
+        .map_err(|err| format!("no GPU in /sys/class/drm and could not run lspci: {err}"))?;
+
+    let mut gpus = Vec::new();
+    for line in decode_output(&output).lines() {
+        // Secondary GPUs (e.g. the iGPU beside a discrete card) often show up as a
+        // "Display controller" rather than a VGA one
+        if line.contains("VGA compatible controller")
+            || line.contains("3D controller")
+            || line.contains("Display controller")
+        {
+            let parts: Vec<&str> = line.split('"').collect();
+            if parts.len() >= 6 {
+                gpus.push(GpuInfo {
+                    name: format!("{} {}", parts[3], parts[5]),
+                    vendor: Some(parts[3].to_string()),
+                    driver_version: get_gpu_driver_version_linux(parts[3]),
+                    ..GpuInfo::default()
+                });
+            }
+        }
+    }
+
+    if gpus.is_empty() {
+        return Err("no GPU found in /sys/class/drm or lspci".to_string());
+    }
+    Ok(gpus)
+}
+
+/// Vendors of the graphics chips found in desktops, servers and virtual machines
+const PCI_VENDORS: &[(u16, &str)] = &[
+    (0x10de, "NVIDIA"),
+    (0x1002, "AMD"),
+    (0x8086, "Intel"),
+    (0x1a03, "ASPEED"),
+    (0x102b, "Matrox"),
+    (0x15ad, "VMware"),
+    (0x1af4, "Red Hat"),
+    (0x1234, "QEMU"),
+    (0x1414, "Microsoft"),
+    (0x80ee, "VirtualBox"),
+];
+
+/// Chips whose name can't be guessed from the vendor, mostly virtual and server ones
+const PCI_DEVICES: &[(u16, u16, &str)] = &[
+    (0x1234, 0x1111, "Standard VGA"),
+    (0x1af4, 0x1050, "Virtio GPU"),
+    (0x15ad, 0x0405, "SVGA II Adapter"),
+    (0x80ee, 0xbeef, "VirtualBox Graphics Adapter"),
+    (0x1414, 0x5353, "Hyper-V Video"),
+    (0x1a03, 0x2000, "ASPEED Graphics Family"),
+    (0x102b, 0x0522, "MGA G200e"),
+    (0x102b, 0x0538, "MGA G200eW3"),
+];
+
+/// Where distributions install the PCI ID database, when pciutils or hwdata is present
+const PCI_IDS_PATHS: &[&str] = &["/usr/share/hwdata/pci.ids", "/usr/share/misc/pci.ids"];
+
+/// The PCI GPUs behind `/sys/class/drm/card*`, named from their vendor and device IDs
+fn get_gpu_info_drm() -> Vec<GpuInfo> {
+    let cards = read_drm_cards(Path::new("/sys/class/drm"));
+    if cards.is_empty() {
+        return Vec::new();
+    }
+    let pci_ids = PCI_IDS_PATHS
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok());
+
+    cards
+        .into_iter()
+        .map(|(vendor_id, device_id)| {
+            let (vendor, name) = pci_ids
+                .as_deref()
+                .and_then(|ids| lookup_pci_ids(ids, vendor_id, device_id))
+                .unwrap_or_else(|| pci_id_name(vendor_id, device_id));
+            GpuInfo {
+                driver_version: vendor.as_deref().and_then(get_gpu_driver_version_linux),
+                name,
+                vendor,
+                ..GpuInfo::default()
+            }
+        })
+        .collect()
+}
+
+/// Vendor and device IDs of the `card<N>` entries of a DRM class directory, in card order
+fn read_drm_cards(drm: &Path) -> Vec<(u16, u16)> {
+    let Ok(entries) = fs::read_dir(drm) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        // Skip connectors (`card0-eDP-1`) and render nodes
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("card"))
+                .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort();
+
+    let read_id = |path: PathBuf| {
+        let id = fs::read_to_string(path).ok()?;
+        u16::from_str_radix(id.trim().trim_start_matches("0x"), 16).ok()
+    };
+    cards
+        .into_iter()
+        // Platform framebuffers (e.g. simpledrm) have no PCI IDs
+        .filter_map(|card| {
+            let device = card.join("device");
+            Some((
+                read_id(device.join("vendor"))?,
+                read_id(device.join("device"))?,
+            ))
+        })
+        .collect()
+}
+
+/// Name a PCI device from the built-in tables, showing the raw IDs when it isn't listed
+fn pci_id_name(vendor_id: u16, device_id: u16) -> (Option<String>, String) {
+    let vendor = PCI_VENDORS
+        .iter()
+        .find(|(id, _)| *id == vendor_id)
+        .map(|(_, name)| name.to_string());
+    let device = PCI_DEVICES
+        .iter()
+        .find(|(vendor, device, _)| (*vendor, *device) == (vendor_id, device_id))
+        .map(|(_, _, name)| *name);
+
+    let name = match (&vendor, device) {
+        (Some(vendor), Some(device)) => format!("{vendor} {device}"),
+        (Some(vendor), None) => format!("{vendor} GPU [{vendor_id:04x}:{device_id:04x}]"),
+        (None, _) => format!("GPU [{vendor_id:04x}:{device_id:04x}]"),
+    };
+    (vendor, name)
+}
+
+/// Find a device in a `pci.ids` database, where devices are tab-indented under their vendor:
+///
+/// ```text
+/// 10de  NVIDIA Corporation
+/// \t2882  AD107 [GeForce RTX 4060]
+/// ```
+fn lookup_pci_ids(ids: &str, vendor_id: u16, device_id: u16) -> Option<(Option<String>, String)> {
+    let vendor_prefix = format!("{vendor_id:04x}  ");
+    let device_prefix = format!("\t{device_id:04x}  ");
+
+    let mut lines = ids
+        .lines()
+        .skip_while(|line| !line.starts_with(&vendor_prefix));
+    let vendor = lines.next()?.strip_prefix(&vendor_prefix)?.trim();
+    let device = lines
+        .take_while(|line| line.starts_with('\t') || line.starts_with('#'))
+        .find_map(|line| line.strip_prefix(&device_prefix))?
+        .trim();
+
+    Some((Some(vendor.to_string()), format!("{vendor} {device}")))
+}
+
+/// Get the driver version for a GPU vendor on Linux
+fn get_gpu_driver_version_linux(vendor: &str) -> Option<String> {
+    let vendor = vendor.to_lowercase();
+
+    if vendor.contains("nvidia") {
+        let output = command_output(
+            Command::new("nvidia-smi")
+                .args(["--query-gpu=driver_version", "--format=csv,noheader"]),
+        )
+        .ok()?;
+        parse_nvidia_driver_version(&decode_output(&output))
+    } else if vendor.contains("amd") || vendor.contains("advanced micro devices") {
+        // Out-of-tree amdgpu builds expose a module version; in-tree ones go through Mesa
+        fs::read_to_string("/sys/module/amdgpu/version")
+            .ok()
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty())
+            .or_else(|| {
+                let output = command_output(Command::new("glxinfo").arg("-B")).ok()?;
+                parse_glxinfo_driver_version(&decode_output(&output))
+            })
+    } else {
+        None
+    }
+}
+
+/// Parse `nvidia-smi --query-gpu=driver_version --format=csv,noheader` output
+fn parse_nvidia_driver_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Parse the Mesa version out of the `OpenGL version string` line of `glxinfo`
+fn parse_glxinfo_driver_version(output: &str) -> Option<String> {
+    let version = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("OpenGL version string:"))?;
+
+    version
+        .find("Mesa")
+        .map(|start| version[start..].trim().to_string())
+}
+
+/// Get GPU information on macOS (multiple GPUs)
+fn get_gpu_info_macos_list() -> Result<Vec<GpuInfo>, String> {
+    command_output(Command::new("system_profiler").args(["SPDisplaysDataType", "-json"]))
+        .map(|output| parse_macos_gpus(&decode_output(&output)))
+        .map_err(|err| format!("could not run system_profiler: {err}"))
+}
+
+/// The parts of `system_profiler SPDisplaysDataType -json` read for the GPU list
+#[derive(Deserialize)]
+struct SpDisplays {
+    #[serde(rename = "SPDisplaysDataType", default)]
+    adapters: Vec<SpDisplayAdapter>,
+}
+
+/// One graphics adapter; the displays attached to it are nested under `spdisplays_ndrvs`
+#[derive(Deserialize)]
+struct SpDisplayAdapter {
+    #[serde(rename = "_name")]
+    name: Option<String>,
+    sppci_model: Option<String>,
+    /// e.g. `sppci_vendor_Apple` or `NVIDIA (0x10de)`
+    spdisplays_vendor: Option<String>,
+    /// Dedicated (`4 GB`) or, for integrated GPUs, shared (`1536 MB`) memory
+    spdisplays_vram: Option<String>,
+    spdisplays_vram_shared: Option<String>,
+}
+
+/// Parse every adapter of `system_profiler SPDisplaysDataType -json`
+fn parse_macos_gpus(output: &str) -> Vec<GpuInfo> {
+    let Ok(displays) = serde_json::from_str::<SpDisplays>(output) else {
+        return Vec::new();
+    };
+
+    displays
+        .adapters
+        .into_iter()
+        .filter_map(|adapter| {
+            let name = adapter.sppci_model.or(adapter.name)?;
+            let vendor = adapter
+                .spdisplays_vendor
+                .map(|vendor| {
+                    let vendor = vendor.strip_prefix("sppci_vendor_").unwrap_or(&vendor);
+                    let vendor = vendor.split(" (").next().unwrap_or(vendor);
+                    vendor.to_string()
+                })
+                .or_else(|| guess_gpu_vendor(&name));
+            let vram_total = adapter
+                .spdisplays_vram
+                .or(adapter.spdisplays_vram_shared)
+                .and_then(|vram| parse_vram_size(&vram));
+            Some(GpuInfo {
+                name,
+                vendor,
+                vram_total,
+                ..GpuInfo::default()
+            })
+        })
+        .collect()
+}
+
+/// Vendor from a GPU name, for adapters that don't report one
+fn guess_gpu_vendor(name: &str) -> Option<String> {
+    let name = name.to_lowercase();
+    let vendor = if name.contains("nvidia") {
+        "NVIDIA"
+    } else if name.contains("amd") || name.contains("radeon") {
+        "AMD"
+    } else if name.contains("intel") {
+        "Intel"
+    } else if name.contains("apple") {
+        "Apple"
+    } else {
+        return None;
+    };
+    Some(vendor.to_string())
+}
+
+/// Parse sizes like `1536 MB` or `8 GB` (system_profiler means binary units)
+fn parse_vram_size(size: &str) -> Option<u64> {
+    let (amount, unit) = size.trim().split_once(' ')?;
+    let amount: u64 = amount.parse().ok()?;
+    let scale = match unit {
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    Some(amount * scale)
+}
+
+/// Sample the network interfaces and compute their transfer rates since the last sample
+fn collect_interfaces(collector: &mut Collector) -> Vec<NetInterface> {
+    // Refreshing the list also picks up interfaces that appeared or went away
+    collector.networks.refresh_list();
+
+    let now = Instant::now();
+    let elapsed = now
+        .duration_since(collector.networks_sampled_at)
+        .as_secs_f64();
+    collector.networks_sampled_at = now;
+
+    let rate = |bytes: u64| {
+        if elapsed > 0.0 {
+            (bytes as f64 / elapsed) as u64
+        } else {
+            0
+        }
+    };
+
+    let addresses = list_afinet_netifas().unwrap_or_default();
+    let address = |name: &str, v4: bool| {
+        let mut matching = addresses
+            .iter()
+            .filter(|(interface, ip)| interface == name && ip.is_ipv4() == v4)
+            .map(|(_, ip)| ip);
+        // Link-local IPv6 addresses are only a fallback
+        let first = matching.clone().next();
+        matching
+            .find(|ip| !is_link_local(ip))
+            .or(first)
+            .map(|ip| ip.to_string())
+    };
+
+    let totals = &mut collector.network_totals;
+    let include_loopback = collector.include_loopback;
+    let mut interfaces: Vec<NetInterface> = collector
+        .networks
+        .iter()
+        .filter(|(name, _)| include_loopback || !is_loopback(name))
+        .map(|(name, data)| {
+            let (rx_total, tx_total) = totals.entry(name.clone()).or_default();
+            let mac = data.mac_address();
+            NetInterface {
+                name: name.clone(),
+                rx_rate: rate(data.received()),
+                tx_rate: rate(data.transmitted()),
+                rx_total: rx_total.observe(data.total_received()),
+                tx_total: tx_total.observe(data.total_transmitted()),
+                ipv4: address(name, true),
+                ipv6: address(name, false),
+                mac: (!mac.is_unspecified()).then(|| mac.to_string()),
+            }
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+    interfaces
+}
+
+/// Get the connected displays
+fn get_display_info_list() -> Vec<DisplayInfo> {
+    if cfg!(target_os = "windows") {
+        get_display_info_windows_list()
+    } else if cfg!(target_os = "linux") {
+        // Without a reachable X server (e.g. on a console), fall back to the kernel's view
+        let displays = command_output(Command::new("xrandr").arg("--query"))
+            .map(|output| parse_xrandr_displays(&decode_output(&output)))
+            .unwrap_or_default();
+        if displays.is_empty() {
+            get_display_info_drm()
+        } else {
+            displays
+        }
+    } else if cfg!(target_os = "macos") {
+        command_output(Command::new("system_profiler").arg("SPDisplaysDataType"))
+            .map(|output| parse_macos_displays(&decode_output(&output)))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Connected outputs in `/sys/class/drm`, each at its preferred (first listed) mode
+fn get_display_info_drm() -> Vec<DisplayInfo> {
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter(|path| {
+            fs::read_to_string(path.join("status")).is_ok_and(|status| status.trim() == "connected")
+        })
+        .filter_map(|path| {
+            // Connectors are named `card0-eDP-1`
+            let name = path.file_name()?.to_str()?;
+            let (_, connector) = name.split_once('-')?;
+            let modes = fs::read_to_string(path.join("modes")).ok()?;
+            let (width, height) = modes.lines().next()?.split_once('x')?;
+
+            Some(DisplayInfo {
+                name: connector.to_string(),
+                width: width.parse().ok()?,
+                height: height
+                    .trim_end_matches(|c: char| !c.is_ascii_digit())
+                    .parse()
+                    .ok()?,
+                primary: false,
+            })
+        })
+        .collect()
+}
+
+/// Parse the displays of `system_profiler SPDisplaysDataType`, nested under their GPU:
+///
+/// ```text
+///       Displays:
+///         Color LCD:
+///           Resolution: 2560 x 1600 Retina
+///           Main Display: Yes
+/// ```
+fn parse_macos_displays(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    let mut name = String::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_suffix(':') {
+            name = heading.to_string();
+        } else if let Some(resolution) = trimmed.strip_prefix("Resolution:") {
+            let mut numbers = resolution
+                .split_whitespace()
+                .filter_map(|word| word.parse::<u32>().ok());
+            if let (Some(width), Some(height)) = (numbers.next(), numbers.next()) {
+                displays.push(DisplayInfo {
+                    name: name.clone(),
+                    width,
+                    height,
+                    primary: false,
+                });
+            }
+        } else if trimmed == "Main Display: Yes" {
+            if let Some(display) = displays.last_mut() {
+                display.primary = true;
+            }
+        }
+    }
+    displays
+}
+
+/// Parse the active outputs of `xrandr --query`.
+///
+/// Output lines look like `HDMI-1 connected primary 2560x1440+0+0 (normal ...) ...`;
+/// disconnected outputs and connected ones without a mode are skipped.
+fn parse_xrandr_displays(output: &str) -> Vec<DisplayInfo> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let name = words.next()?;
+            if words.next()? != "connected" {
+                return None;
+            }
+
+            let mut primary = false;
+            let geometry = words.find(|word| {
+                primary |= *word == "primary";
+                word.contains('x') && word.contains('+')
+            })?;
+            let (width, rest) = geometry.split_once('x')?;
+            let height = rest.split('+').next()?;
+
+            Some(DisplayInfo {
+                name: name.to_string(),
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+                primary,
+            })
+        })
+        .collect()
+}
+
+/// Get the displays on Windows through `EnumDisplayMonitors`
+#[cfg(windows)]
+fn get_display_info_windows_list() -> Vec<DisplayInfo> {
+    use windows_sys::Win32::Foundation::{BOOL, LPARAM, RECT};
+    use windows_sys::Win32::Graphics::Gdi::{
+        EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+    };
+
+    const MONITORINFOF_PRIMARY: u32 = 1;
+
+    unsafe extern "system" fn callback(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let displays = unsafe { &mut *(data as *mut Vec<DisplayInfo>) };
+
+        let mut info: MONITORINFOEXW = unsafe { std::mem::zeroed() };
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if unsafe { GetMonitorInfoW(monitor, &mut info as *mut _ as *mut MONITORINFO) } != 0 {
+            let rect = info.monitorInfo.rcMonitor;
+            let len = info.szDevice.iter().position(|&c| c == 0).unwrap_or(0);
+            displays.push(DisplayInfo {
+                name: String::from_utf16_lossy(&info.szDevice[..len]),
+                width: (rect.right - rect.left).unsigned_abs(),
+                height: (rect.bottom - rect.top).unsigned_abs(),
+                primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            });
+        }
+
+        1
+    }
+
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(callback),
+            &mut displays as *mut _ as LPARAM,
+        );
+    }
+
+    displays
+}
+
+#[cfg(not(windows))]
+fn get_display_info_windows_list() -> Vec<DisplayInfo> {
+    Vec::new()
+}
+
+/// Get the default audio output device
+fn get_audio_device() -> Option<String> {
+    if cfg!(target_os = "windows") {
+        get_audio_device_windows()
+    } else if cfg!(target_os = "linux") {
+        get_audio_device_pulse().or_else(get_audio_device_wireplumber)
+    } else if cfg!(target_os = "macos") {
+        let output = command_output(Command::new("system_profiler").arg("SPAudioDataType")).ok()?;
+        parse_macos_default_output(&decode_output(&output))
+    } else {
+        None
+    }
+}
+
+/// Default sink description from PulseAudio (or PipeWire's pulse server)
+fn get_audio_device_pulse() -> Option<String> {
+    let output = command_output(Command::new("pactl").arg("get-default-sink"))
+        .ok()
+        .filter(|output| output.status.success())?;
+    let sink = decode_output(&output).trim().to_string();
+    if sink.is_empty() {
+        return None;
+    }
+
+    let sinks = command_output(Command::new("pactl").args(["list", "sinks"])).ok();
+    sinks
+        .and_then(|output| parse_pactl_description(&decode_output(&output), &sink))
+        .or(Some(sink))
+}
+
+/// Find the `Description:` of the sink named `sink` in `pactl list sinks`
+fn parse_pactl_description(output: &str, sink: &str) -> Option<String> {
+    let mut in_sink = false;
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("Name:") {
+            in_sink = name.trim() == sink;
+        } else if let Some(description) = line.strip_prefix("Description:") {
+            if in_sink {
+                return Some(description.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Default sink description from WirePlumber
+fn get_audio_device_wireplumber() -> Option<String> {
+    let output = command_output(Command::new("wpctl").args(["inspect", "@DEFAULT_AUDIO_SINK@"]))
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    decode_output(&output).lines().find_map(|line| {
+        let value = line
+            .trim_start_matches([' ', '*'])
+            .strip_prefix("node.description = ")?;
+        Some(value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Find the device marked `Default Output Device: Yes` in `system_profiler SPAudioDataType`
+fn parse_macos_default_output(output: &str) -> Option<String> {
+    let mut device = None;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed.strip_suffix(':') {
+            device = Some(name.to_string());
+        } else if trimmed == "Default Output Device: Yes" {
+            return device;
+        }
+    }
+    None
+}
+
+/// First sound device reported by wmic (Windows has no default flag there)
+fn get_audio_device_windows() -> Option<String> {
+    let output = command_output(Command::new("wmic").args(["sounddev", "get", "name"])).ok()?;
+
+    decode_output(&output)
+        .lines()
+        .skip(1)
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Package managers counted by listing their packages, one per output line:
+/// reported name, program and arguments
+const PACKAGE_MANAGERS: [(&str, &str, &[&str]); 6] = [
+    ("dpkg", "dpkg-query", &["-f", ".\n", "-W"]),
+    ("rpm", "rpm", &["-qa"]),
+    ("pacman", "pacman", &["-Qq"]),
+    ("flatpak", "flatpak", &["list", "--columns=application"]),
+    ("pkg", "pkg", &["info"]),
+    ("pkg_info", "pkg_info", &[]),
+];
+
+/// Where Homebrew keeps one directory per installed formula
+const BREW_CELLARS: [&str; 3] = [
+    "/opt/homebrew/Cellar",
+    "/usr/local/Cellar",
+    "/home/linuxbrew/.linuxbrew/Cellar",
+];
+
+/// Count the installed packages of every package manager found, skipping empty ones
+fn get_package_counts() -> Vec<(String, usize)> {
+    if cfg!(target_os = "windows") {
+        return Vec::new();
+    }
+
+    // Missing managers fail to spawn right away; the others run side by side
+    let mut counts: Vec<(String, usize)> = thread::scope(|scope| {
+        let probes: Vec<_> = PACKAGE_MANAGERS
+            .iter()
+            .map(|(name, program, args)| {
+                scope.spawn(move || {
+                    let output = command_output(Command::new(program).args(*args))
+                        .ok()
+                        .filter(|output| output.status.success())?;
+                    let count = decode_output(&output)
+                        .lines()
+                        .filter(|line| !line.trim().is_empty())
+                        .count();
+                    Some((name.to_string(), count))
+                })
+            })
+            .collect();
+        probes
+            .into_iter()
+            .filter_map(|probe| probe.join().ok().flatten())
+            .collect()
+    });
+
+    // Listing formulae through `brew` itself takes about a second
+    let brew = BREW_CELLARS
+        .iter()
+        .filter_map(|cellar| fs::read_dir(cellar).ok())
+        .map(|entries| entries.flatten().count())
+        .sum();
+    counts.push(("brew".to_string(), brew));
+
+    counts.retain(|(_, count)| *count > 0);
+    counts
+}
+
+/// Time an external command gets before it is killed, unless `command_timeout` is set
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The current command timeout in milliseconds; probes run on several threads
+static COMMAND_TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT.as_millis() as u64);
+
+/// Change how long `command_output` lets a command run (`command_timeout`)
+pub fn set_command_timeout(timeout: Duration) {
+    let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+    COMMAND_TIMEOUT_MS.store(millis, Ordering::Relaxed);
+}
+
+/// Run a command with the configured timeout, so a wedged tool can't hold up collection
+fn command_output(command: &mut Command) -> io::Result<Output> {
+    let timeout = Duration::from_millis(COMMAND_TIMEOUT_MS.load(Ordering::Relaxed));
+    output_with_timeout(command, timeout)
+}
+
+/// Run a command and collect its stdout, killing it once `timeout` has passed
+fn output_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Drain stdout meanwhile, or a full pipe would keep the child from exiting
+    let mut stdout = child.stdout.take().ok_or(io::ErrorKind::BrokenPipe)?;
+    let reader = thread::spawn(move || {
+        let mut bytes = Vec::new();
+        stdout.read_to_end(&mut bytes).map(|_| bytes)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {timeout:?}"),
+            ));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let stdout = reader
+        .join()
+        .map_err(|_| io::Error::other("stdout reader panicked"))??;
+    Ok(Output {
+        status,
+        stdout,
+        stderr: Vec::new(),
+    })
+}
+
+/// Root of the cgroup hierarchy as seen from inside a container
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Read the memory and CPU limits of the current cgroup (v2, then v1), keeping
+/// only those below the host values
+fn get_cgroup_limits(host_memory: u64, host_cpus: usize) -> Option<CgroupLimits> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let root = Path::new(CGROUP_ROOT);
+    let read = |path: &str| fs::read_to_string(root.join(path)).ok();
+
+    let memory = read("memory.max")
+        .and_then(|max| parse_cgroup_memory_max(&max))
+        .or_else(|| {
+            read("memory/memory.limit_in_bytes").and_then(|max| parse_cgroup_memory_max(&max))
+        })
+        .filter(|limit| *limit < host_memory);
+
+    let cpus = read("cpu.max")
+        .and_then(|max| parse_cgroup_cpu_max(&max))
+        .or_else(|| {
+            let quota = read("cpu/cpu.cfs_quota_us")?;
+            let period = read("cpu/cpu.cfs_period_us")?;
+            parse_cgroup_v1_cpu_quota(&quota, &period)
+        })
+        .filter(|cpus| *cpus < host_cpus as f64);
+
+    if memory.is_none() && cpus.is_none() {
+        None
+    } else {
+        Some(CgroupLimits { memory, cpus })
+    }
+}
+
+/// Memory used by the current cgroup, in bytes
+fn get_cgroup_memory_used() -> Option<u64> {
+    let root = Path::new(CGROUP_ROOT);
+    fs::read_to_string(root.join("memory.current"))
+        .or_else(|_| fs::read_to_string(root.join("memory/memory.usage_in_bytes")))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parse `memory.max` (v2) or `memory.limit_in_bytes` (v1); `max` means unlimited
+fn parse_cgroup_memory_max(content: &str) -> Option<u64> {
+    content.trim().parse().ok()
+}
+
+/// Parse `cpu.max` (v2), `<quota> <period>` in microseconds or `max <period>` when unlimited
+fn parse_cgroup_cpu_max(content: &str) -> Option<f64> {
+    let (quota, period) = content.trim().split_once(' ')?;
+    let quota: f64 = quota.parse().ok()?;
+    let period: f64 = period.trim().parse().ok()?;
+
+    (quota > 0.0 && period > 0.0).then(|| quota / period)
+}
+
+/// Parse the v1 `cpu.cfs_quota_us` and `cpu.cfs_period_us` files; a quota of -1 means unlimited
+fn parse_cgroup_v1_cpu_quota(quota: &str, period: &str) -> Option<f64> {
+    let quota: i64 = quota.trim().parse().ok()?;
+    let period: i64 = period.trim().parse().ok()?;
+
+    (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+}
+
+/// Get the memory pressure level (macOS only)
+fn get_memory_pressure() -> Option<MemPressure> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+
+    let output = command_output(&mut Command::new("vm_stat")).ok()?;
+    parse_vm_stat_pressure(&decode_output(&output))
+}
+
+/// Derive the pressure level from `vm_stat` page counts.
+///
+/// Memory the kernel can hand out without swapping (free, inactive and
+/// speculative pages) is compared against all tracked pages: 25% or more
+/// is normal, 10% or more is a warning, anything below is critical.
+fn parse_vm_stat_pressure(output: &str) -> Option<MemPressure> {
+    let pages = |key: &str| {
+        output.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?;
+            value.trim().trim_end_matches('.').parse::<u64>().ok()
+        })
+    };
+
+    let free = pages("Pages free")?;
+    let inactive = pages("Pages inactive")?;
+    let speculative = pages("Pages speculative").unwrap_or(0);
+    let active = pages("Pages active")?;
+    let wired = pages("Pages wired down")?;
+    let compressed = pages("Pages occupied by compressor").unwrap_or(0);
+
+    let total = free + inactive + speculative + active + wired + compressed;
+    if total == 0 {
+        return None;
+    }
+
+    let available = (free + inactive + speculative) * 100 / total;
+    Some(match available {
+        25.. => MemPressure::Normal,
+        10.. => MemPressure::Warning,
+        _ => MemPressure::Critical,
+    })
+}
+
+/// Hottest CPU sensor reading, in °C
+fn get_cpu_temp(collector: &mut Collector) -> Option<f32> {
+    const CPU_LABELS: [&str; 6] = ["cpu", "core", "package", "k10temp", "tctl", "tdie"];
+
+    collector.components.refresh();
+    collector
+        .components
+        .iter()
+        .filter(|component| {
+            let label = component.label().to_lowercase();
+            CPU_LABELS.iter().any(|name| label.contains(name))
+        })
+        .map(|component| component.temperature())
+        .filter(|temp| temp.is_finite() && *temp > 0.0)
+        .reduce(f32::max)
+}
+
+/// Time allowed for `nvidia-smi`, which can stall while the driver wakes the card
+const NVIDIA_SMI_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Update the GPU readings: name, temperature and VRAM of NVIDIA cards from `nvidia-smi`
+/// in listing order, the temperature of the first AMD card from its hwmon sensor (Linux only)
+fn refresh_gpu_sensors(gpus: &mut [GpuInfo]) {
+    if !cfg!(target_os = "linux") || gpus.is_empty() {
+        return;
+    }
+
+    let is_vendor = |gpu: &GpuInfo, names: &[&str]| {
+        let vendor = gpu.vendor.as_deref().unwrap_or_default().to_lowercase();
+        names.iter().any(|name| vendor.contains(name))
+    };
+
+    // Without nvidia-smi (or when it fails) the lspci names stay and VRAM is unknown
+    let nvidia = if gpus.iter().any(|gpu| is_vendor(gpu, &["nvidia"])) {
+        output_with_timeout(
+            Command::new("nvidia-smi").args([
+                "--query-gpu=name,memory.used,memory.total,temperature.gpu",
+                "--format=csv,noheader,nounits",
+            ]),
+            NVIDIA_SMI_TIMEOUT,
+        )
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_nvidia_smi(&decode_output(&output)))
+        .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let mut nvidia = nvidia.into_iter();
+    let mut amd_temp = get_amd_gpu_temp();
+
+    for gpu in gpus {
+        if is_vendor(gpu, &["nvidia"]) {
+            let reading = nvidia.next().unwrap_or_default();
+            if let Some(name) = reading.name {
+                gpu.name = name;
+            }
+            gpu.temperature = reading.temperature;
+            gpu.vram_used = reading.vram_used;
+            gpu.vram_total = reading.vram_total;
+        } else if is_vendor(gpu, &["amd", "advanced micro devices"]) {
+            gpu.temperature = amd_temp.take();
+        } else {
+            gpu.temperature = None;
+        }
+    }
+}
+
+/// One GPU's row of `nvidia-smi --query-gpu`
+#[derive(Debug, Default, PartialEq)]
+struct NvidiaReading {
+    name: Option<String>,
+    temperature: Option<f32>,
+    vram_used: Option<u64>,
+    vram_total: Option<u64>,
 }
 
-/// System information structure
-#[derive(Debug, Clone)]
-pub struct SystemInfo {
-    pub os_name: String,
-    pub os_version: String,
-    pub os_arch: String,
-    pub kernel_version: String,
-    pub hostname: String,
-    pub username: String,
-    pub uptime: String,
-    pub cpus: Vec<CpuInfo>,
-    pub memory_total: u64,
-    pub memory_used: u64,
-    pub gpus: Vec<GpuInfo>,
-    pub local_ip: String,
-    pub shell: String,
-    pub terminal: String,
-}
+/// Parse `nvidia-smi --query-gpu=name,memory.used,memory.total,temperature.gpu
+/// --format=csv,noheader,nounits`, one line per GPU with memory in MiB; fields the card
+/// doesn't support read `[N/A]`
+fn parse_nvidia_smi(output: &str) -> Vec<NvidiaReading> {
+    const MIB: u64 = 1024 * 1024;
 
-impl SystemInfo {
-    /// Collect system information
-    pub fn collect() -> AppResult<Self> {
-        let mut sys = System::new_all();
-        sys.refresh_all();
-
-        // Basic system information
-        let os_name = System::name().unwrap_or_else(|| "Unknown".to_string());
-        let os_version = System::os_version().unwrap_or_else(|| "Unknown".to_string());
-        let os_arch = std::env::consts::ARCH.into();
-        let kernel_version = System::kernel_version().unwrap_or_else(|| "Unknown".to_string());
-        let hostname = System::host_name().unwrap_or_else(|| "Unknown".to_string());
-        let username = env::var("USER")
-            .or_else(|_| env::var("USERNAME"))
-            .unwrap_or_else(|_| "Unknown".to_string());
-
-        // Uptime
-        let uptime_seconds = System::uptime();
-        let uptime = format_uptime(uptime_seconds);
-
-        // CPU information
-        let cpus = collect_cpu_info(&sys);
-
-        // Memory information
-        let memory_total = sys.total_memory();
-        let memory_used = sys.used_memory();
-
-        // GPU information
-        let gpus = get_gpu_info_list();
-
-        // Local IP address
-        let local_ip = get_local_ip();
-
-        // Shell and Terminal information
-        let shell = get_shell_info();
-        let terminal = get_terminal_info();
-
-        Ok(Self {
-            os_name,
-            os_version,
-            os_arch,
-            kernel_version,
-            hostname,
-            username,
-            uptime,
-            cpus,
-            memory_total,
-            memory_used,
-            gpus,
-            local_ip,
-            shell,
-            terminal,
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let mib = |index: usize| {
+                let value = fields.get(index)?.parse::<u64>().ok()?;
+                Some(value * MIB)
+            };
+            NvidiaReading {
+                name: fields
+                    .first()
+                    .filter(|name| !name.is_empty() && !name.starts_with('['))
+                    .map(|name| name.to_string()),
+                vram_used: mib(1),
+                vram_total: mib(2).filter(|total| *total > 0),
+                temperature: fields.get(3).and_then(|temp| temp.parse().ok()),
+            }
         })
-    }
+        .collect()
 }
 
-/// Format uptime
-fn format_uptime(seconds: u64) -> String {
-    let days = seconds / 86400;
-    let hours = (seconds % 86400) / 3600;
-    let minutes = (seconds % 3600) / 60;
+/// First `temp1_input` reading of the amdgpu hwmon sensors of `card0`
+fn get_amd_gpu_temp() -> Option<f32> {
+    let mut sensors: Vec<PathBuf> = fs::read_dir("/sys/class/drm/card0/device/hwmon")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join("temp1_input"))
+        .collect();
+    sensors.sort();
+
+    sensors
+        .iter()
+        .find_map(|path| parse_hwmon_temp(&fs::read_to_string(path).ok()?))
+}
+
+/// Parse a hwmon `temp*_input` value, given in millidegrees Celsius
+fn parse_hwmon_temp(contents: &str) -> Option<f32> {
+    let millidegrees = contents.trim().parse::<i64>().ok()?;
+    Some(millidegrees as f32 / 1000.0).filter(|temp| *temp > 0.0)
+}
+
+/// Format a Celsius reading in the requested unit, e.g. `65°C` or `149°F`
+pub fn format_temp(celsius: f32, unit: TempUnit) -> String {
+    match unit {
+        TempUnit::C => format!("{celsius:.0}°C"),
+        TempUnit::F => format!("{:.0}°F", celsius * 9.0 / 5.0 + 32.0),
+    }
+}
 
-    if days > 0 {
-        format!("{}d {}h {}m", days, hours, minutes)
-    } else if hours > 0 {
-        format!("{}h {}m", hours, minutes)
+/// Whether the system runs on mains power; `None` without a power adapter (desktops)
+fn get_ac_power() -> Option<bool> {
+    if cfg!(target_os = "linux") {
+        get_ac_power_sysfs().or_else(|| {
+            read_acpi_states("/proc/acpi/ac_adapter", "on-line", "off-line")
+                .into_iter()
+                .reduce(|a, b| a || b)
+        })
+    } else if cfg!(target_os = "macos") {
+        let output = command_output(Command::new("pmset").args(["-g", "batt"])).ok()?;
+        parse_pmset_power_source(&decode_output(&output))
     } else {
-        format!("{}m", minutes)
+        None
     }
 }
 
-/// Collect CPU information
-fn collect_cpu_info(sys: &System) -> Vec<CpuInfo> {
-    let mut cpu_map = std::collections::HashMap::new();
+/// Online state of the `Mains` supplies in `/sys/class/power_supply`, `true` if any is online
+fn get_ac_power_sysfs() -> Option<bool> {
+    fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            fs::read_to_string(path.join("type")).is_ok_and(|kind| kind.trim() == "Mains")
+        })
+        .filter_map(|path| {
+            parse_power_supply_online(&fs::read_to_string(path.join("online")).ok()?)
+        })
+        .reduce(|a, b| a || b)
+}
 
-    // Group CPUs by model to handle multi-core processors
-    for cpu in sys.cpus() {
-        let model = cpu.brand().to_string();
-        let frequency = cpu.frequency();
+/// Parse a power supply `online` attribute, `1` or `0`
+fn parse_power_supply_online(contents: &str) -> Option<bool> {
+    match contents.trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
 
-        let entry = cpu_map.entry(model.clone()).or_insert(CpuInfo {
-            model,
-            cores: 0,
-            frequency,
-        });
-        entry.cores += 1;
+/// Whether the laptop lid is open, from ACPI (Linux only)
+fn get_lid_open() -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
     }
 
-    cpu_map.into_values().collect()
+    read_acpi_states("/proc/acpi/button/lid", "open", "closed")
+        .into_iter()
+        .next()
 }
 
-/// Get GPU information list
-fn get_gpu_info_list() -> Vec<GpuInfo> {
-    if cfg!(target_os = "windows") {
-        get_gpu_info_windows_list()
-    } else if cfg!(target_os = "linux") {
-        get_gpu_info_linux_list()
-    } else if cfg!(target_os = "macos") {
-        get_gpu_info_macos_list()
+/// The `state` of every device under a `/proc/acpi` directory, in name order
+fn read_acpi_states(dir: &str, on: &str, off: &str) -> Vec<bool> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| {
+            let contents = fs::read_to_string(path.join("state")).ok()?;
+            parse_acpi_state(&contents, on, off)
+        })
+        .collect()
+}
+
+/// Parse a `state:   on-line` style ACPI file into `true` for `on` and `false` for `off`
+fn parse_acpi_state(contents: &str, on: &str, off: &str) -> Option<bool> {
+    let state = contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("state:"))?
+        .trim();
+
+    if state == on {
+        Some(true)
+    } else if state == off {
+        Some(false)
     } else {
-        vec![GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        }]
+        None
     }
 }
 
-/// Get GPU information on Windows system (multiple GPUs)
-fn get_gpu_info_windows_list() -> Vec<GpuInfo> {
-    let mut gpus = Vec::new();
+/// Parse the `Now drawing from 'AC Power'` line of `pmset -g batt`
+fn parse_pmset_power_source(output: &str) -> Option<bool> {
+    let source = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Now drawing from"))?;
+
+    if source.contains("AC Power") {
+        Some(true)
+    } else if source.contains("Battery Power") {
+        Some(false)
+    } else {
+        None
+    }
+}
 
-    match Command::new("wmic")
-        .args(&[
+/// Charge of the first system battery; `None` on desktops
+fn get_battery_info() -> Option<BatteryInfo> {
+    if cfg!(target_os = "linux") {
+        get_battery_sysfs()
+    } else if cfg!(target_os = "macos") {
+        let output = command_output(Command::new("pmset").args(["-g", "batt"])).ok()?;
+        parse_pmset_battery(&decode_output(&output))
+    } else if cfg!(target_os = "windows") {
+        let output = command_output(Command::new("wmic").args([
             "path",
-            "win32_VideoController",
+            "Win32_Battery",
             "get",
-            "name,AdapterCompatibility",
+            "BatteryStatus,EstimatedChargeRemaining,EstimatedRunTime",
             "/format:value",
-        ])
-        .output()
-    {
-        Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let mut current_gpu = GpuInfo {
-                name: String::new(),
-                vendor: String::new(),
-            };
-
-            for line in output_str.lines() {
-                let line = line.trim();
-                if line.starts_with("AdapterCompatibility=")
-                    && !line.trim_end_matches("AdapterCompatibility=").is_empty()
-                {
-                    current_gpu.vendor = line
-                        .trim_start_matches("AdapterCompatibility=")
-                        .trim()
-                        .to_string();
-                } else if line.starts_with("Name=") && !line.trim_end_matches("Name=").is_empty() {
-                    current_gpu.name = line.trim_start_matches("Name=").trim().to_string();
-
-                    // If we have both name and vendor, add to list
-                    if !current_gpu.name.is_empty() {
-                        gpus.push(current_gpu.clone());
-                        current_gpu = GpuInfo {
-                            name: String::new(),
-                            vendor: String::new(),
-                        };
-                    }
-                }
-            }
-        }
-        Err(_) => {}
+        ]))
+        .ok()?;
+        parse_wmic_battery(&decode_output(&output))
+    } else {
+        None
     }
+}
 
-    if gpus.is_empty() {
-        gpus.push(GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        });
-    }
+/// The first `Battery` supply in `/sys/class/power_supply`, skipping those of
+/// peripherals like mice
+fn get_battery_sysfs() -> Option<BatteryInfo> {
+    let read = |path: &Path, name: &str| {
+        fs::read_to_string(path.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
 
-    gpus
+    let mut paths: Vec<PathBuf> = fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            read(path, "type").as_deref() == Some("Battery")
+                && read(path, "scope").as_deref() != Some("Device")
+        })
+        .collect();
+    paths.sort();
+    let path = paths.first()?;
+
+    let state = read(path, "status").unwrap_or_else(|| "Unknown".to_string());
+    let number = |name: &str| read(path, name)?.parse::<f64>().ok();
+    // Batteries report either energy (µWh, µW) or charge (µAh, µA)
+    let time_remaining = battery_seconds_left(
+        &state,
+        number("energy_now").or_else(|| number("charge_now")),
+        number("energy_full").or_else(|| number("charge_full")),
+        number("power_now").or_else(|| number("current_now")),
+    );
+
+    Some(BatteryInfo {
+        percent: read(path, "capacity")?.parse().ok()?,
+        state,
+        time_remaining,
+    })
 }
 
-/// Get GPU information on Linux system (multiple GPUs)
-fn get_gpu_info_linux_list() -> Vec<GpuInfo> {
-    let mut gpus = Vec::new();
+/// Seconds until empty (discharging) or full (charging) at the present rate
+fn battery_seconds_left(
+    state: &str,
+    now: Option<f64>,
+    full: Option<f64>,
+    rate: Option<f64>,
+) -> Option<u64> {
+    let rate = rate.filter(|rate| *rate > 0.0)?;
+    let hours = match state {
+        "Discharging" => now? / rate,
+        "Charging" => (full? - now?).max(0.0) / rate,
+        _ => return None,
+    };
+    Some((hours * 3600.0) as u64)
+}
 
-    match Command::new("lspci").args(&["-mm"]).output() {
-        Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            for line in output_str.lines() {
-                if line.contains("VGA compatible controller") || line.contains("3D controller") {
-                    let parts: Vec<&str> = line.split('"').collect();
-                    if parts.len() >= 6 {
-                        gpus.push(GpuInfo {
-                            name: format!("{} {}", parts[3], parts[5]),
-                            vendor: parts[3].to_string(),
-                        });
-                    }
-                }
-            }
-        }
-        Err(_) => {}
-    }
+/// Parse the battery line of `pmset -g batt`, e.g.
+/// ` -InternalBattery-0 (id=4653155)\t87%; discharging; 4:32 remaining present: true`
+fn parse_pmset_battery(output: &str) -> Option<BatteryInfo> {
+    let line = output
+        .lines()
+        .find(|line| line.contains("InternalBattery"))?;
+    let (_, status) = line.split_once('\t')?;
+    let mut parts = status.split(';').map(str::trim);
 
-    if gpus.is_empty() {
-        gpus.push(GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        });
-    }
+    let percent = parts.next()?.strip_suffix('%')?.parse().ok()?;
+    let state = match parts.next()? {
+        "charging" => "Charging",
+        "discharging" => "Discharging",
+        "charged" => "Full",
+        "AC attached" => "Not charging",
+        _ => "Unknown",
+    };
+    // `(no estimate)` while the estimate is being calculated
+    let time_remaining = parts
+        .next()
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|time| time.split_once(':'))
+        .and_then(|(hours, minutes)| {
+            Some(hours.parse::<u64>().ok()? * 3600 + minutes.parse::<u64>().ok()? * 60)
+        })
+        .filter(|_| matches!(state, "Charging" | "Discharging"));
 
-    gpus
+    Some(BatteryInfo {
+        percent,
+        state: state.to_string(),
+        time_remaining,
+    })
 }
 
-/// Get GPU information on macOS system (multiple GPUs)
-fn get_gpu_info_macos_list() -> Vec<GpuInfo> {
-    let mut gpus = Vec::new();
+/// Parse `Win32_Battery` values from `wmic ... /format:value`
+fn parse_wmic_battery(output: &str) -> Option<BatteryInfo> {
+    let value = |key: &str| {
+        output.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix(key)?
+                .strip_prefix('=')?
+                .parse::<u64>()
+                .ok()
+        })
+    };
 
-    match Command::new("system_profiler")
-        .args(&["SPDisplaysDataType", "-json"])
-        .output()
-    {
-        Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-
-            // Simple parsing to find all GPU names
-            let mut pos = 0;
-            while let Some(start) = output_str[pos..].find("\"_name\" : \"") {
-                let start = pos + start + 11;
-                if let Some(end) = output_str[start..].find('"') {
-                    let gpu_name = output_str[start..start + end].to_string();
-                    gpus.push(GpuInfo {
-                        name: gpu_name.clone(),
-                        vendor: if gpu_name.to_lowercase().contains("nvidia") {
-                            "NVIDIA".to_string()
-                        } else if gpu_name.to_lowercase().contains("amd")
-                            || gpu_name.to_lowercase().contains("radeon")
-                        {
-                            "AMD".to_string()
-                        } else if gpu_name.to_lowercase().contains("intel") {
-                            "Intel".to_string()
-                        } else {
-                            "Unknown".to_string()
-                        },
-                    });
-                    pos = start + end;
-                } else {
-                    break;
-                }
+    let percent = value("EstimatedChargeRemaining")? as f32;
+    let state = match value("BatteryStatus") {
+        Some(1) => "Discharging",
+        Some(3) => "Full",
+        Some(2 | 6..=9) => "Charging",
+        _ => "Unknown",
+    };
+    // 71582788 minutes stands for "unknown", reported while on AC
+    let time_remaining = value("EstimatedRunTime")
+        .filter(|minutes| *minutes < 71_582_788 && state == "Discharging")
+        .map(|minutes| minutes * 60);
+
+    Some(BatteryInfo {
+        percent,
+        state: state.to_string(),
+        time_remaining,
+    })
+}
+
+/// Sample space and inode usage of the mounted disks
+fn collect_disks(collector: &mut Collector) -> Vec<DiskInfo> {
+    // Refreshing the list also picks up filesystems that were mounted or unmounted
+    collector.disks.refresh_list();
+
+    collector
+        .disks
+        .iter()
+        .filter(|disk| disk.total_space() > 0)
+        .map(|disk| {
+            let (inodes_total, inodes_used) = get_inode_usage(disk.mount_point())
+                .map_or((None, None), |(total, used)| (Some(total), Some(used)));
+
+            DiskInfo {
+                mount_point: disk.mount_point().display().to_string(),
+                fs_type: disk.file_system().to_string_lossy().into_owned(),
+                total: disk.total_space(),
+                used: disk.total_space().saturating_sub(disk.available_space()),
+                inodes_total,
+                inodes_used,
             }
-        }
-        Err(_) => {}
+        })
+        .collect()
+}
+
+/// Get the total and used inode counts of the filesystem mounted at `path`
+#[cfg(unix)]
+fn get_inode_usage(path: &Path) -> Option<(u64, u64)> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
     }
 
-    if gpus.is_empty() {
-        gpus.push(GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        });
+    // Filesystems with dynamic inode allocation (btrfs, zfs) report zero
+    let total = stat.f_files as u64;
+    if total == 0 {
+        return None;
     }
+    let free = stat.f_ffree as u64;
 
-    gpus
+    Some((total, total.saturating_sub(free)))
+}
+
+/// Windows has no cheap equivalent of statvfs inode counts
+#[cfg(not(unix))]
+fn get_inode_usage(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+fn is_loopback(name: &str) -> bool {
+    name == "lo" || name.starts_with("lo0") || name.to_lowercase().contains("loopback")
+}
+
+/// IPv6 `fe80::/10`, which every interface has and which only works on its own link
+fn is_link_local(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(_) => false,
+        IpAddr::V6(ip) => ip.segments()[0] & 0xffc0 == 0xfe80,
+    }
 }
 
 /// Get local IP address
-fn get_local_ip() -> String {
-    match local_ip() {
-        Ok(ip) => ip.to_string(),
-        Err(_) => "Unknown IP".to_string(),
+fn get_local_ip() -> Result<String, String> {
+    local_ip()
+        .map(|ip| ip.to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// Service answering a plain HTTP GET with the caller's address
+const PUBLIC_IP_HOST: &str = "api.ipify.org";
+
+/// Give up on the public IP lookup after this long, per connect, write and read
+const PUBLIC_IP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Ask ipify for the public IP address; fails when offline or the request times out
+fn get_public_ip() -> Result<String, String> {
+    let failed = |err: std::io::Error| format!("{PUBLIC_IP_HOST}: {err}");
+    let address = (PUBLIC_IP_HOST, 80)
+        .to_socket_addrs()
+        .map_err(failed)?
+        .next()
+        .ok_or_else(|| format!("{PUBLIC_IP_HOST}: no address"))?;
+    let mut stream = TcpStream::connect_timeout(&address, PUBLIC_IP_TIMEOUT).map_err(failed)?;
+    stream
+        .set_read_timeout(Some(PUBLIC_IP_TIMEOUT))
+        .map_err(failed)?;
+    stream
+        .set_write_timeout(Some(PUBLIC_IP_TIMEOUT))
+        .map_err(failed)?;
+
+    write!(
+        stream,
+        "GET / HTTP/1.0\r\nHost: {PUBLIC_IP_HOST}\r\nConnection: close\r\n\r\n"
+    )
+    .map_err(failed)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(failed)?;
+    parse_public_ip_response(&response)
+        .ok_or_else(|| format!("{PUBLIC_IP_HOST}: unexpected response"))
+}
+
+/// The address in the body of a successful HTTP response
+fn parse_public_ip_response(response: &str) -> Option<String> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.lines().next()?.split_whitespace().nth(1)?;
+    if status != "200" {
+        return None;
     }
+    body.trim().parse::<IpAddr>().ok().map(|ip| ip.to_string())
 }
 
 /// Get shell information
-fn get_shell_info() -> String {
-    // Try to get shell from environment variables
-    if let Ok(shell) = env::var("SHELL") {
-        // Extract shell name from path
-        if let Some(shell_name) = shell.split('/').last() {
-            return shell_name.to_string();
-        }
-        return shell;
+fn get_shell_info() -> Option<String> {
+    // The login shell on Unix, and in MSYS or Git Bash on Windows
+    if let Some(shell) = shell_basename(env::var("SHELL").ok().as_deref()) {
+        return Some(shell);
     }
 
-    // Windows specific shell detection
-    if cfg!(target_os = "windows") {
-        // Check for PowerShell
-        if env::var("PSModulePath").is_ok() {
-            return "PowerShell".to_string();
-        }
+    // Otherwise the process that started us, e.g. powershell or cmd
+    if let Some(parent) = get_parent_process_name() {
+        return Some(parent);
+    }
 
-        // Check for Command Prompt
+    if cfg!(target_os = "windows") {
         if let Ok(comspec) = env::var("COMSPEC") {
-            if let Some(shell_name) = comspec.split('\\').last() {
-                return shell_name.replace(".exe", "");
+            if let Some(shell_name) = comspec.rsplit('\\').next() {
+                return Some(shell_name.replace(".exe", ""));
             }
         }
-
-        return "cmd".to_string();
+        return Some("cmd".to_string());
     }
 
-    // Unix-like systems fallback
-    if let Ok(output) = Command::new("ps")
-        .args(&["-p", &std::process::id().to_string(), "-o", "comm="])
-        .output()
-    {
-        let shell = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !shell.is_empty() {
-            return shell;
-        }
-    }
+    None
+}
+
+/// Name of the shell in a `SHELL` value such as `/usr/bin/zsh`; `None` when unset or empty
+fn shell_basename(shell: Option<&str>) -> Option<String> {
+    let name = shell?.trim_end_matches('/').rsplit('/').next()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Name of the parent process, without an `.exe` suffix
+fn get_parent_process_name() -> Option<String> {
+    let mut sys = System::new();
+    let pid = Pid::from_u32(std::process::id());
+    sys.refresh_process(pid);
+    let parent = sys.process(pid)?.parent()?;
+    sys.refresh_process(parent);
 
-    "Unknown Shell".to_string()
+    let name = sys.process(parent)?.name();
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    (!name.is_empty()).then(|| name.to_string())
 }
 
-/// Get terminal information
-fn get_terminal_info() -> String {
+/// Identify the terminal emulator from the variables it sets, falling back to `TERM`
+fn detect_terminal() -> Option<String> {
     // Check common terminal environment variables
     let terminal_vars = [
         "TERM_PROGRAM",          // macOS Terminal, iTerm2, etc.
@@ -351,14 +2576,14 @@ fn get_terminal_info() -> String {
     for var in &terminal_vars {
         if let Ok(value) = env::var(var) {
             match *var {
-                "TERM_PROGRAM" => return value,
-                "TERMINAL_EMULATOR" => return value,
-                "KONSOLE_VERSION" => return "Konsole".to_string(),
-                "GNOME_TERMINAL_SCREEN" => return "GNOME Terminal".to_string(),
-                "XTERM_VERSION" => return format!("xterm {}", value),
-                "ALACRITTY_SOCKET" => return "Alacritty".to_string(),
-                "KITTY_WINDOW_ID" => return "Kitty".to_string(),
-                "WEZTERM_EXECUTABLE" => return "WezTerm".to_string(),
+                "TERM_PROGRAM" => return Some(value),
+                "TERMINAL_EMULATOR" => return Some(value),
+                "KONSOLE_VERSION" => return Some("Konsole".to_string()),
+                "GNOME_TERMINAL_SCREEN" => return Some("GNOME Terminal".to_string()),
+                "XTERM_VERSION" => return Some(format!("xterm {}", value)),
+                "ALACRITTY_SOCKET" => return Some("Alacritty".to_string()),
+                "KITTY_WINDOW_ID" => return Some("Kitty".to_string()),
+                "WEZTERM_EXECUTABLE" => return Some("WezTerm".to_string()),
                 _ => {}
             }
         }
@@ -368,59 +2593,53 @@ fn get_terminal_info() -> String {
     if cfg!(target_os = "windows") {
         // Check for Windows Terminal
         if env::var("WT_SESSION").is_ok() {
-            return "Windows Terminal".to_string();
+            return Some("Windows Terminal".to_string());
         }
 
         // Check for ConEmu
         if env::var("ConEmuPID").is_ok() {
-            return "ConEmu".to_string();
+            return Some("ConEmu".to_string());
         }
 
         // Check for Cmder
         if env::var("CMDER_ROOT").is_ok() {
-            return "Cmder".to_string();
+            return Some("Cmder".to_string());
         }
 
         // Try to detect through parent process on Windows
-        if let Ok(output) = Command::new("wmic")
-            .args(&[
-                "process",
-                "where",
-                &format!("ProcessId={}", std::process::id()),
-                "get",
-                "ParentProcessId",
-                "/format:value",
-            ])
-            .output()
-        {
-            let output_str = String::from_utf8_lossy(&output.stdout);
+        if let Ok(output) = command_output(Command::new("wmic").args([
+            "process",
+            "where",
+            &format!("ProcessId={}", std::process::id()),
+            "get",
+            "ParentProcessId",
+            "/format:value",
+        ])) {
+            let output_str = decode_output(&output);
             for line in output_str.lines() {
                 if let Some(ppid_str) = line.strip_prefix("ParentProcessId=") {
                     if let Ok(ppid) = ppid_str.trim().parse::<u32>() {
-                        if let Ok(parent_output) = Command::new("wmic")
-                            .args(&[
-                                "process",
-                                "where",
-                                &format!("ProcessId={}", ppid),
-                                "get",
-                                "Name",
-                                "/format:value",
-                            ])
-                            .output()
-                        {
-                            let parent_str = String::from_utf8_lossy(&parent_output.stdout);
+                        if let Ok(parent_output) = command_output(Command::new("wmic").args([
+                            "process",
+                            "where",
+                            &format!("ProcessId={}", ppid),
+                            "get",
+                            "Name",
+                            "/format:value",
+                        ])) {
+                            let parent_str = decode_output(&parent_output);
                             for parent_line in parent_str.lines() {
                                 if let Some(name) = parent_line.strip_prefix("Name=") {
                                     let name = name.trim();
                                     if !name.is_empty() {
-                                        return match name {
+                                        return Some(match name {
                                             "WindowsTerminal.exe" => "Windows Terminal".to_string(),
                                             "ConEmu64.exe" | "ConEmu.exe" => "ConEmu".to_string(),
                                             "cmd.exe" => "Command Prompt".to_string(),
                                             "powershell.exe" => "PowerShell".to_string(),
                                             "pwsh.exe" => "PowerShell Core".to_string(),
                                             _ => name.replace(".exe", ""),
-                                        };
+                                        });
                                     }
                                 }
                             }
@@ -430,37 +2649,597 @@ fn get_terminal_info() -> String {
             }
         }
 
-        return "Command Prompt".to_string();
+        return Some("Command Prompt".to_string());
     }
 
-    // Unix-like systems: try to get terminal from TERM or parent process
-    if let Ok(term) = env::var("TERM") {
-        // Common terminal identifiers
-        match term.as_str() {
-            "xterm-256color" | "xterm" => {
-                // Try to get more specific terminal info
-                if let Ok(output) = Command::new("ps")
-                    .args(&["-o", "comm=", "-p", &std::process::id().to_string()])
-                    .output()
-                {
-                    let parent = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    if !parent.is_empty() && parent != "sh" && parent != "bash" {
-                        return parent;
-                    }
-                }
-                return "xterm".to_string();
-            }
-            "screen" => return "GNU Screen".to_string(),
-            "tmux" => return "tmux".to_string(),
-            _ => {
-                if term.contains("kitty") {
-                    return "Kitty".to_string();
-                } else if term.contains("alacritty") {
-                    return "Alacritty".to_string();
-                }
-            }
+    // Unix-like systems: fall back to the terminal type in TERM
+    let term = env::var("TERM").ok().filter(|term| !term.is_empty())?;
+    Some(match term.as_str() {
+        "xterm-256color" | "xterm" => "xterm".to_string(),
+        "screen" => "GNU Screen".to_string(),
+        "tmux" => "tmux".to_string(),
+        _ if term.contains("kitty") => "Kitty".to_string(),
+        _ if term.contains("alacritty") => "Alacritty".to_string(),
+        _ => term,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn output_with_timeout_kills_a_hung_command() {
+        let started = Instant::now();
+        let error = output_with_timeout(Command::new("sleep").arg("5"), Duration::from_millis(100))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn decode_bytes_reads_utf16le_with_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(utf16le("Name\r\nNVIDIA GeForce RTX 4070\r\n"));
+        assert_eq!(decode_bytes(&bytes), "Name\r\nNVIDIA GeForce RTX 4070\r\n");
+    }
+
+    #[test]
+    fn decode_bytes_detects_utf16le_without_bom() {
+        assert_eq!(decode_bytes(&utf16le("31.0.15.5222")), "31.0.15.5222");
+    }
+
+    #[test]
+    fn decode_bytes_passes_utf8_through() {
+        assert_eq!(decode_bytes("Intel® Iris Xe".as_bytes()), "Intel® Iris Xe");
+        assert_eq!(decode_bytes(b""), "");
+    }
+
+    #[test]
+    fn parse_kernel_build_drops_the_release() {
+        let proc_version = "Linux version 6.9.7-arch1-1 (linux@archlinux) (gcc (GCC) 14.1.1 20240522, GNU ld (GNU Binutils) 2.42.0) #1 SMP PREEMPT_DYNAMIC Fri, 28 Jun 2024 04:32:50 +0000\n";
+        assert_eq!(
+            parse_kernel_build(proc_version).as_deref(),
+            Some(
+                "(linux@archlinux) (gcc (GCC) 14.1.1 20240522, GNU ld (GNU Binutils) 2.42.0) #1 SMP PREEMPT_DYNAMIC Fri, 28 Jun 2024 04:32:50 +0000"
+            )
+        );
+        assert_eq!(parse_kernel_build("Linux version 6.9.7"), None);
+        assert_eq!(parse_kernel_build("Darwin Kernel Version 23.5.0"), None);
+    }
+
+    #[test]
+    fn parse_bsd_kernel_build_drops_system_and_release() {
+        let kern_version = "FreeBSD 14.0-RELEASE #0 releng/14.0-n265380-f9716eee8ab4: Fri Nov 10 05:57:23 UTC 2023\n    root@releng1.nyi.freebsd.org:/usr/obj/usr/src/amd64.amd64/sys/GENERIC\n";
+        assert_eq!(
+            parse_bsd_kernel_build(kern_version).as_deref(),
+            Some("#0 releng/14.0-n265380-f9716eee8ab4: Fri Nov 10 05:57:23 UTC 2023")
+        );
+        assert_eq!(parse_bsd_kernel_build("FreeBSD 14.0-RELEASE"), None);
+        assert_eq!(parse_bsd_kernel_build(""), None);
+    }
+
+    #[test]
+    fn parse_nvidia_driver_version_takes_the_first_line() {
+        assert_eq!(
+            parse_nvidia_driver_version("\n550.90.07\n550.90.07\n").as_deref(),
+            Some("550.90.07")
+        );
+        assert_eq!(parse_nvidia_driver_version(" \n"), None);
+    }
+
+    #[test]
+    fn parse_glxinfo_driver_version_finds_mesa() {
+        let glxinfo = "\
+name of display: :0
+OpenGL vendor string: AMD
+OpenGL renderer string: AMD Radeon 780M (radeonsi, gfx1103_r1, LLVM 17.0.6, DRM 3.57)
+OpenGL version string: 4.6 (Compatibility Profile) Mesa 24.1.2-arch1.1
+";
+        assert_eq!(
+            parse_glxinfo_driver_version(glxinfo).as_deref(),
+            Some("Mesa 24.1.2-arch1.1")
+        );
+
+        let proprietary = "OpenGL version string: 4.6.0 NVIDIA 550.90.07\n";
+        assert_eq!(parse_glxinfo_driver_version(proprietary), None);
+        assert_eq!(parse_glxinfo_driver_version(""), None);
+    }
+
+    #[test]
+    fn parse_xrandr_displays_reads_active_outputs() {
+        let xrandr = "\
+Screen 0: minimum 320 x 200, current 4480 x 1440, maximum 16384 x 16384
+eDP-1 connected 1920x1200+2560+0 (normal left inverted right x axis y axis) 302mm x 189mm
+   1920x1200     60.00*+  59.88
+HDMI-1 connected primary 2560x1440+0+0 (normal left inverted right x axis y axis) 597mm x 336mm
+   2560x1440     59.95*+
+DP-1 disconnected (normal left inverted right x axis y axis)
+DP-2 connected (normal left inverted right x axis y axis)
+   1920x1080     60.00 +
+";
+        let displays: Vec<_> = parse_xrandr_displays(xrandr)
+            .into_iter()
+            .map(|d| (d.name, d.width, d.height, d.primary))
+            .collect();
+        assert_eq!(
+            displays,
+            [
+                ("eDP-1".to_string(), 1920, 1200, false),
+                ("HDMI-1".to_string(), 2560, 1440, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn count_kernel_modules_skips_malformed_lines() {
+        let proc_modules = "\
+snd_hda_intel 61440 3 - Live 0x0000000000000000
+amdgpu 12115968 21 - Live 0x0000000000000000
+broken-line
+nvme_core xyz 1 nvme, Live 0x0000000000000000
+
+kvm 1400832 1 kvm_amd, Live 0x0000000000000000
+";
+        assert_eq!(count_kernel_modules(proc_modules), 3);
+        assert_eq!(count_kernel_modules(""), 0);
+    }
+
+    #[test]
+    fn parse_cgroup_memory_max_treats_max_as_unlimited() {
+        assert_eq!(parse_cgroup_memory_max("536870912\n"), Some(536_870_912));
+        assert_eq!(parse_cgroup_memory_max("max\n"), None);
+    }
+
+    #[test]
+    fn parse_cgroup_cpu_max_divides_quota_by_period() {
+        assert_eq!(parse_cgroup_cpu_max("150000 100000\n"), Some(1.5));
+        assert_eq!(parse_cgroup_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cgroup_cpu_max("100000"), None);
+    }
+
+    #[test]
+    fn parse_cgroup_v1_cpu_quota_treats_minus_one_as_unlimited() {
+        assert_eq!(parse_cgroup_v1_cpu_quota("200000\n", "100000\n"), Some(2.0));
+        assert_eq!(parse_cgroup_v1_cpu_quota("-1\n", "100000\n"), None);
+        assert_eq!(parse_cgroup_v1_cpu_quota("50000", "0"), None);
+    }
+
+    #[test]
+    fn parse_scaling_governor_reads_a_single_word() {
+        assert_eq!(
+            parse_scaling_governor("schedutil\n").as_deref(),
+            Some("schedutil")
+        );
+        assert_eq!(parse_scaling_governor("\n"), None);
+        assert_eq!(parse_scaling_governor("performance powersave"), None);
+    }
+
+    #[test]
+    fn parse_nvidia_smi_reads_each_gpu() {
+        let output = "\
+NVIDIA GeForce RTX 4070, 1024, 12282, 45
+[N/A], [N/A], [N/A], [N/A]
+";
+        assert_eq!(
+            parse_nvidia_smi(output),
+            [
+                NvidiaReading {
+                    name: Some("NVIDIA GeForce RTX 4070".to_string()),
+                    temperature: Some(45.0),
+                    vram_used: Some(1024 * 1024 * 1024),
+                    vram_total: Some(12282 * 1024 * 1024),
+                },
+                NvidiaReading::default(),
+            ]
+        );
+        assert!(parse_nvidia_smi("\n").is_empty());
+    }
+
+    #[test]
+    fn parse_hwmon_temp_scales_millidegrees() {
+        assert_eq!(parse_hwmon_temp("54000\n"), Some(54.0));
+        assert_eq!(parse_hwmon_temp("41500"), Some(41.5));
+        assert_eq!(parse_hwmon_temp("0\n"), None);
+        assert_eq!(parse_hwmon_temp("N/A"), None);
+    }
+
+    #[test]
+    fn parse_acpi_state_matches_on_and_off() {
+        assert_eq!(
+            parse_acpi_state("state:                   on-line\n", "on-line", "off-line"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_acpi_state("state:      closed\n", "open", "closed"),
+            Some(false)
+        );
+        assert_eq!(parse_acpi_state("state: unknown\n", "open", "closed"), None);
+        assert_eq!(parse_acpi_state("", "open", "closed"), None);
+    }
+
+    #[test]
+    fn parse_pmset_power_source_reads_the_drawing_line() {
+        let on_ac = "\
+Now drawing from 'AC Power'
+ -InternalBattery-0 (id=4653155)	76%; charging; 0:47 remaining present: true
+";
+        assert_eq!(parse_pmset_power_source(on_ac), Some(true));
+
+        let on_battery = "\
+Now drawing from 'Battery Power'
+ -InternalBattery-0 (id=4653155)	76%; discharging; 5:12 remaining present: true
+";
+        assert_eq!(parse_pmset_power_source(on_battery), Some(false));
+        assert_eq!(
+            parse_pmset_power_source("Now drawing from 'UPS Power'"),
+            None
+        );
+        assert_eq!(parse_pmset_power_source(""), None);
+    }
+
+    #[test]
+    fn parse_macos_gpus_reads_every_adapter() {
+        let output = r#"{
+  "SPDisplaysDataType" : [
+    {
+      "_name" : "kHW_AMDRadeonPro5500MItem",
+      "sppci_model" : "AMD Radeon Pro 5500M",
+      "spdisplays_vendor" : "AMD (0x1002)",
+      "spdisplays_vram" : "4 GB"
+    },
+    {
+      "_name" : "Intel UHD Graphics 630",
+      "spdisplays_vendor" : "sppci_vendor_intel",
+      "spdisplays_vram_shared" : "1536 MB"
+    },
+    {
+      "_name" : "Apple M2",
+      "spdisplays_ndrvs" : [ { "_name" : "Color LCD" } ]
+    }
+  ]
+}"#;
+        let gpus: Vec<_> = parse_macos_gpus(output)
+            .into_iter()
+            .map(|gpu| (gpu.name, gpu.vendor, gpu.vram_total))
+            .collect();
+        assert_eq!(
+            gpus,
+            [
+                (
+                    "AMD Radeon Pro 5500M".to_string(),
+                    Some("AMD".to_string()),
+                    Some(4 * 1024 * 1024 * 1024)
+                ),
+                (
+                    "Intel UHD Graphics 630".to_string(),
+                    Some("intel".to_string()),
+                    Some(1536 * 1024 * 1024)
+                ),
+                ("Apple M2".to_string(), Some("Apple".to_string()), None),
+            ]
+        );
+        assert!(parse_macos_gpus("not json").is_empty());
+    }
+
+    fn vm_stat(free: u64, inactive: u64, active: u64, wired: u64) -> String {
+        format!(
+            "\
+Mach Virtual Memory Statistics: (page size of 16384 bytes)
+Pages free:                               {free}.
+Pages active:                             {active}.
+Pages inactive:                           {inactive}.
+Pages speculative:                             0.
+Pages wired down:                         {wired}.
+Pages occupied by compressor:                  0.
+"
+        )
+    }
+
+    #[test]
+    fn parse_vm_stat_pressure_grades_available_memory() {
+        assert_eq!(
+            parse_vm_stat_pressure(&vm_stat(200, 100, 500, 200)),
+            Some(MemPressure::Normal)
+        );
+        assert_eq!(
+            parse_vm_stat_pressure(&vm_stat(100, 50, 650, 200)),
+            Some(MemPressure::Warning)
+        );
+        assert_eq!(
+            parse_vm_stat_pressure(&vm_stat(50, 40, 710, 200)),
+            Some(MemPressure::Critical)
+        );
+    }
+
+    #[test]
+    fn parse_vm_stat_pressure_needs_the_page_counts() {
+        assert_eq!(parse_vm_stat_pressure(&vm_stat(0, 0, 0, 0)), None);
+        assert_eq!(parse_vm_stat_pressure("Pages free: 100.\n"), None);
+    }
+
+    #[test]
+    fn format_bytes_with_steps_at_the_unit_boundary() {
+        assert_eq!(format_bytes_with(1023, ByteUnit::Binary), "1023 B");
+        assert_eq!(format_bytes_with(1024, ByteUnit::Binary), "1.0 KiB");
+        assert_eq!(format_bytes_with(1000, ByteUnit::Binary), "1000 B");
+        assert_eq!(format_bytes_with(999, ByteUnit::Decimal), "999 B");
+        assert_eq!(format_bytes_with(1000, ByteUnit::Decimal), "1.0 KB");
+        assert_eq!(format_bytes_with(1024, ByteUnit::Decimal), "1.0 KB");
+        assert_eq!(format_bytes_with(1024 * 1024, ByteUnit::Binary), "1.0 MiB");
+        assert_eq!(format_bytes_with(1_000_000, ByteUnit::Decimal), "1.0 MB");
+    }
+
+    #[test]
+    fn format_bytes_with_stops_at_the_largest_unit() {
+        assert_eq!(format_bytes_with(0, ByteUnit::Decimal), "0 B");
+        assert_eq!(
+            format_bytes_with(2048 * 1024u64.pow(4), ByteUnit::Binary),
+            "2048.0 TiB"
+        );
+    }
+
+    #[test]
+    fn format_uptime_with_compact() {
+        assert_eq!(format_uptime_with(0, UptimeFormat::Compact), "0s");
+        assert_eq!(format_uptime_with(45, UptimeFormat::Compact), "45s");
+        assert_eq!(format_uptime_with(7500, UptimeFormat::Compact), "2h 5m");
+        assert_eq!(
+            format_uptime_with(273_900, UptimeFormat::Compact),
+            "3d 4h 5m"
+        );
+    }
+
+    #[test]
+    fn format_uptime_with_verbose() {
+        assert_eq!(format_uptime_with(0, UptimeFormat::Verbose), "0 seconds");
+        assert_eq!(format_uptime_with(1, UptimeFormat::Verbose), "1 second");
+        assert_eq!(format_uptime_with(59, UptimeFormat::Verbose), "59 seconds");
+        assert_eq!(format_uptime_with(60, UptimeFormat::Verbose), "1 minute");
+        assert_eq!(
+            format_uptime_with(7500, UptimeFormat::Verbose),
+            "2 hours, 5 minutes"
+        );
+        assert_eq!(
+            format_uptime_with(90_060, UptimeFormat::Verbose),
+            "1 day, 1 hour, 1 minute"
+        );
+        assert_eq!(format_uptime_with(172_800, UptimeFormat::Verbose), "2 days");
+    }
+
+    #[test]
+    fn load_average_is_only_read_outside_windows() {
+        let collector = &mut Collector::new();
+        let mut info = SystemInfo::collect_static(collector);
+        info.refresh_dynamic(collector);
+        assert_eq!(info.load_average.is_some(), !cfg!(windows));
+        if let Some((one, five, fifteen)) = info.load_average {
+            assert!(one >= 0.0 && five >= 0.0 && fifteen >= 0.0);
+        }
+    }
+
+    #[test]
+    fn shell_basename_takes_the_last_path_component() {
+        assert_eq!(shell_basename(Some("/usr/bin/zsh")).as_deref(), Some("zsh"));
+        assert_eq!(shell_basename(Some("fish")).as_deref(), Some("fish"));
+        assert_eq!(shell_basename(Some("/bin/bash/")).as_deref(), Some("bash"));
+    }
+
+    #[test]
+    fn shell_basename_leaves_unset_or_empty_shell_to_the_fallback() {
+        assert_eq!(shell_basename(None), None);
+        assert_eq!(shell_basename(Some("")), None);
+        assert_eq!(shell_basename(Some("/")), None);
+    }
+
+    #[test]
+    fn parent_process_has_a_name() {
+        let name = get_parent_process_name();
+        assert!(name.is_some_and(|name| !name.is_empty()));
+    }
+
+    #[test]
+    fn collect_disks_reports_the_primary_disk() {
+        let disks = collect_disks(&mut Collector::new());
+        let primary = crate::config::default_primary_disk();
+        let disk = disks
+            .iter()
+            .find(|disk| disk.mount_point == primary)
+            .unwrap_or_else(|| panic!("no disk mounted at {primary}"));
+        assert!(disk.total > 0);
+        assert!(disk.used <= disk.total);
+    }
+
+    /// A scratch directory under the system temp dir, removed again by the caller
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rsysfetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn read_drm_cards_reads_pci_ids_of_cards_only() {
+        let drm = scratch_dir("drm");
+        for (card, vendor, device) in [("card1", "0x10de", "0x2882"), ("card0", "0x8086", "0xa7a0")]
+        {
+            let dir = drm.join(card).join("device");
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("vendor"), format!("{vendor}\n")).unwrap();
+            fs::write(dir.join("device"), format!("{device}\n")).unwrap();
         }
+        // A connector and a platform framebuffer without PCI IDs
+        fs::create_dir_all(drm.join("card0-eDP-1")).unwrap();
+        fs::create_dir_all(drm.join("card2").join("device")).unwrap();
+
+        assert_eq!(read_drm_cards(&drm), [(0x8086, 0xa7a0), (0x10de, 0x2882)]);
+        assert!(read_drm_cards(&drm.join("missing")).is_empty());
+        fs::remove_dir_all(&drm).unwrap();
+    }
+
+    const PCI_IDS: &str = "\
+# List of PCI ID's
+10de  NVIDIA Corporation
+\t1f9d  TU117M [GeForce GTX 1650 Mobile / Max-Q]
+# comment between devices
+\t2882  AD107 [GeForce RTX 4060]
+\t\t1043 8902  Dual GeForce RTX 4060
+10df  Emulex Corporation
+\t2882  Not a GPU
+";
+
+    #[test]
+    fn lookup_pci_ids_finds_the_device_under_its_vendor() {
+        assert_eq!(
+            lookup_pci_ids(PCI_IDS, 0x10de, 0x2882),
+            Some((
+                Some("NVIDIA Corporation".to_string()),
+                "NVIDIA Corporation AD107 [GeForce RTX 4060]".to_string()
+            ))
+        );
+        assert_eq!(lookup_pci_ids(PCI_IDS, 0x10de, 0x9999), None);
+        assert_eq!(lookup_pci_ids(PCI_IDS, 0x1002, 0x2882), None);
+    }
+
+    #[test]
+    fn pci_id_name_falls_back_to_raw_ids() {
+        assert_eq!(
+            pci_id_name(0x1af4, 0x1050),
+            (
+                Some("Red Hat".to_string()),
+                "Red Hat Virtio GPU".to_string()
+            )
+        );
+        assert_eq!(
+            pci_id_name(0x10de, 0x2882),
+            (
+                Some("NVIDIA".to_string()),
+                "NVIDIA GPU [10de:2882]".to_string()
+            )
+        );
+        assert_eq!(
+            pci_id_name(0xabcd, 0x0001),
+            (None, "GPU [abcd:0001]".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_video_controllers_reads_wmic_records() {
+        let wmic = "\r\r\n\r\r\nAdapterCompatibility=NVIDIA\r\r\nDriverVersion=31.0.15.5222\r\r\nName=NVIDIA GeForce RTX 4070\r\r\n\r\r\n\r\r\nAdapterCompatibility=Intel Corporation\r\r\nDriverVersion=\r\r\nName=Intel(R) UHD Graphics 770\r\r\n\r\r\n";
+        let gpus: Vec<_> = parse_video_controllers(wmic)
+            .into_iter()
+            .map(|gpu| (gpu.name, gpu.vendor, gpu.driver_version))
+            .collect();
+        assert_eq!(
+            gpus,
+            [
+                (
+                    "NVIDIA GeForce RTX 4070".to_string(),
+                    Some("NVIDIA".to_string()),
+                    Some("31.0.15.5222".to_string())
+                ),
+                (
+                    "Intel(R) UHD Graphics 770".to_string(),
+                    Some("Intel Corporation".to_string()),
+                    None
+                ),
+            ]
+        );
     }
 
-    "Unknown Terminal".to_string()
+    #[test]
+    fn parse_video_controllers_reads_powershell_records() {
+        // The last record has no trailing blank line and no driver
+        let powershell = "\
+AdapterCompatibility=Microsoft Corporation
+DriverVersion=10.0.22621.1
+Name=Microsoft Basic Display Adapter
+
+AdapterCompatibility=
+DriverVersion=
+Name=
+
+AdapterCompatibility=Red Hat, Inc.
+Name=Red Hat VirtIO GPU DOD controller";
+        let gpus: Vec<_> = parse_video_controllers(powershell)
+            .into_iter()
+            .map(|gpu| (gpu.name, gpu.vendor, gpu.driver_version))
+            .collect();
+        assert_eq!(
+            gpus,
+            [
+                (
+                    "Microsoft Basic Display Adapter".to_string(),
+                    Some("Microsoft Corporation".to_string()),
+                    Some("10.0.22621.1".to_string())
+                ),
+                (
+                    "Red Hat VirtIO GPU DOD controller".to_string(),
+                    Some("Red Hat, Inc.".to_string()),
+                    None
+                ),
+            ]
+        );
+        assert!(parse_video_controllers("").is_empty());
+    }
+
+    #[test]
+    fn read_secure_boot_reads_the_efivar_value() {
+        let efivars = scratch_dir("efivars");
+        let var = efivars.join("SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c");
+        fs::write(
+            efivars.join("BootCurrent-8be4df61-93ca-11d2-aa0d-00e098032b8c"),
+            [6, 0, 0, 0, 1, 0],
+        )
+        .unwrap();
+
+        fs::write(&var, [6, 0, 0, 0, 1]).unwrap();
+        assert_eq!(read_secure_boot(&efivars), Some(true));
+        fs::write(&var, [6, 0, 0, 0, 0]).unwrap();
+        assert_eq!(read_secure_boot(&efivars), Some(false));
+        fs::write(&var, [6, 0, 0, 0]).unwrap();
+        assert_eq!(read_secure_boot(&efivars), None);
+
+        fs::remove_file(&var).unwrap();
+        assert_eq!(read_secure_boot(&efivars), None);
+        fs::remove_dir_all(&efivars).unwrap();
+        assert_eq!(read_secure_boot(&efivars), None);
+    }
+
+    #[test]
+    fn read_tpm_reads_the_version() {
+        let tpm = scratch_dir("tpm");
+        assert_eq!(read_tpm(&tpm), None);
+
+        let device = tpm.join("tpm0");
+        fs::create_dir_all(device.join("device")).unwrap();
+        assert_eq!(read_tpm(&tpm).as_deref(), Some("TPM"));
+
+        let caps = "Manufacturer: 0x49465800\nTCG version: 1.2\nFirmware version: 6.40\n";
+        fs::write(device.join("device").join("caps"), caps).unwrap();
+        assert_eq!(read_tpm(&tpm).as_deref(), Some("TPM 1.2"));
+
+        fs::write(device.join("tpm_version_major"), "2\n").unwrap();
+        assert_eq!(read_tpm(&tpm).as_deref(), Some("TPM 2.0"));
+        fs::write(device.join("tpm_version_major"), "1\n").unwrap();
+        assert_eq!(read_tpm(&tpm).as_deref(), Some("TPM 1.0"));
+
+        fs::remove_dir_all(&tpm).unwrap();
+        assert_eq!(read_tpm(&tpm), None);
+    }
+
+    #[test]
+    fn slow_probes_run_first_then_once_per_interval() {
+        let mut collector = Collector::new();
+        let start = Instant::now();
+        assert!(collector.slow_probes_due(start));
+        assert!(!collector.slow_probes_due(start + Duration::from_secs(1)));
+        assert!(collector.slow_probes_due(start + SLOW_PROBE_INTERVAL));
+        assert!(!collector.slow_probes_due(start + SLOW_PROBE_INTERVAL));
+    }
 }
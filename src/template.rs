@@ -0,0 +1,165 @@
+use crate::config::UnknownToken;
+use crate::fields;
+use crate::health;
+use crate::system_info::{SystemInfo, format_bytes, format_uptime};
+use serde_yaml::Value;
+
+/// Template printed by `--oneline` unless the config sets one
+pub const DEFAULT_ONELINE: &str = "{os_name} · {kernel_version} · {cpu_percent}% cpu · {memory} mem · {disk_percent}% disk · up {uptime}";
+
+/// Tokens computed from several fields, in addition to the field registry
+pub const DERIVED: &[(&str, &str)] = &[
+    ("uptime", "Time since boot, e.g. `3d 4h 5m`"),
+    ("cpu_percent", "CPU usage, in whole percent"),
+    ("memory", "Used and total memory, e.g. `11.0 GB/31.0 GB`"),
+    ("memory_percent", "Used memory, in whole percent"),
+    (
+        "disk_percent",
+        "Usage of the `primary_disk` mount (`/`, or `C:\\` on Windows), in whole percent",
+    ),
+];
+
+/// Replace `{field}` tokens with values from the field registry or [`DERIVED`].
+///
+/// Fields that are unknown on this machine, or not plain values (lists, maps),
+/// render as empty; tokens that name no field at all follow `unknown`.
+/// `{disk_percent}` reads the disk mounted at `primary_disk`.
+pub fn render(
+    template: &str,
+    info: &SystemInfo,
+    unknown: UnknownToken,
+    primary_disk: &str,
+) -> String {
+    let values = serde_yaml::to_value(info).unwrap_or(Value::Null);
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+
+        let token = &after[..end];
+        if let Some(value) = derived(token, info, primary_disk) {
+            output.push_str(&value);
+            rest = &after[end + 1..];
+            continue;
+        }
+        match fields::find(token) {
+            Some(field) => output.push_str(&scalar(values.get(field.name))),
+            None if unknown == UnknownToken::Literal => {
+                output.push_str(&rest[start..start + end + 2]);
+            }
+            None => {}
+        }
+        rest = &after[end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Value of a [`DERIVED`] token, `None` for any other token
+fn derived(token: &str, info: &SystemInfo, primary_disk: &str) -> Option<String> {
+    let value = match token {
+        "uptime" => format_uptime(info.uptime_seconds),
+        "cpu_percent" => format!("{:.0}", info.cpu_usage),
+        "memory" => format!(
+            "{}/{}",
+            format_bytes(info.memory_used),
+            format_bytes(info.memory_total)
+        ),
+        "memory_percent" => {
+            format!(
+                "{:.0}",
+                health::percent(info.memory_used, info.memory_total)
+            )
+        }
+        "disk_percent" => info
+            .disks
+            .iter()
+            .find(|disk| disk.mount_point == primary_disk)
+            .map(|disk| format!("{:.0}", health::percent(disk.used, disk.total)))
+            .unwrap_or_default(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn scalar(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(value)) => value.clone(),
+        Some(Value::Number(value)) => value.to_string(),
+        Some(Value::Bool(value)) => value.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn demo() -> SystemInfo {
+        SystemInfo::load_mock(Path::new("fixtures/demo.json")).unwrap()
+    }
+
+    #[test]
+    fn replaces_field_tokens() {
+        assert_eq!(
+            render(
+                "{hostname} · {os_name} {os_version}",
+                &demo(),
+                UnknownToken::Literal,
+                "/"
+            ),
+            "workstation · Arch Linux rolling"
+        );
+    }
+
+    #[test]
+    fn replaces_derived_tokens() {
+        let info = demo();
+        assert_eq!(
+            render(
+                "up {uptime}, {cpu_percent}% cpu",
+                &info,
+                UnknownToken::Literal,
+                "/"
+            ),
+            "up 3d 4h 5m, 27% cpu"
+        );
+        assert_eq!(
+            render("{disk_percent}%", &info, UnknownToken::Literal, "/"),
+            "41%"
+        );
+        assert_eq!(
+            render("{disk_percent}%", &info, UnknownToken::Literal, "/boot"),
+            "15%"
+        );
+    }
+
+    #[test]
+    fn unknown_tokens_render_literally_or_empty() {
+        let info = demo();
+        assert_eq!(
+            render("[{nope}] {hostname}", &info, UnknownToken::Literal, "/"),
+            "[{nope}] workstation"
+        );
+        assert_eq!(
+            render("[{nope}] {hostname}", &info, UnknownToken::Empty, "/"),
+            "[] workstation"
+        );
+    }
+
+    #[test]
+    fn unclosed_brace_is_kept() {
+        assert_eq!(
+            render("{hostname} {os_name", &demo(), UnknownToken::Empty, "/"),
+            "workstation {os_name"
+        );
+    }
+}
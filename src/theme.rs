@@ -0,0 +1,356 @@
+use crate::config::{THEME_PRESETS, ThemeConfig};
+use crate::panel::InfoItem;
+use ratatui::{buffer::Buffer, style::Color, widgets::BorderType};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::str::FromStr;
+
+/// Colors used by the UI, grouped by semantic role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub title: Color,
+    pub label: Color,
+    pub accent: Color,
+    pub logo: Color,
+    pub muted: Color,
+    /// Fill behind the whole UI; `Color::Reset` keeps the terminal background
+    pub background: Color,
+    pub border: BorderType,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            title: Color::Cyan,
+            label: Color::Cyan,
+            accent: Color::Yellow,
+            logo: Color::Red,
+            muted: Color::Gray,
+            background: Color::Reset,
+            border: BorderType::Plain,
+        }
+    }
+}
+
+impl Theme {
+    /// Green-on-black CRT look with double-line borders
+    pub fn retro() -> Self {
+        Self {
+            title: Color::LightGreen,
+            label: Color::Green,
+            accent: Color::LightGreen,
+            logo: Color::Green,
+            muted: Color::DarkGray,
+            background: Color::Black,
+            border: BorderType::Double,
+        }
+    }
+
+    /// Shades of gray only, for terminals or eyes that prefer no color
+    pub fn mono() -> Self {
+        Self {
+            title: Color::White,
+            label: Color::White,
+            accent: Color::Gray,
+            logo: Color::White,
+            muted: Color::DarkGray,
+            background: Color::Reset,
+            border: BorderType::Plain,
+        }
+    }
+
+    /// The Dracula palette: pink and purple on its dark background
+    pub fn dracula() -> Self {
+        Self {
+            title: Color::Rgb(0xff, 0x79, 0xc6),
+            label: Color::Rgb(0xbd, 0x93, 0xf9),
+            accent: Color::Rgb(0x50, 0xfa, 0x7b),
+            logo: Color::Rgb(0xbd, 0x93, 0xf9),
+            muted: Color::Rgb(0x62, 0x72, 0xa4),
+            background: Color::Rgb(0x28, 0x2a, 0x36),
+            border: BorderType::Rounded,
+        }
+    }
+
+    /// Gruvbox dark: warm yellow, aqua and orange
+    pub fn gruvbox() -> Self {
+        Self {
+            title: Color::Rgb(0xfa, 0xbd, 0x2f),
+            label: Color::Rgb(0x8e, 0xc0, 0x7c),
+            accent: Color::Rgb(0xfe, 0x80, 0x19),
+            logo: Color::Rgb(0xfb, 0x49, 0x34),
+            muted: Color::Rgb(0x92, 0x83, 0x74),
+            background: Color::Rgb(0x28, 0x28, 0x28),
+            border: BorderType::Plain,
+        }
+    }
+
+    /// Solarized dark: blue and cyan on base03
+    pub fn solarized() -> Self {
+        Self {
+            title: Color::Rgb(0x26, 0x8b, 0xd2),
+            label: Color::Rgb(0x2a, 0xa1, 0x98),
+            accent: Color::Rgb(0xb5, 0x89, 0x00),
+            logo: Color::Rgb(0xcb, 0x4b, 0x16),
+            muted: Color::Rgb(0x58, 0x6e, 0x75),
+            background: Color::Rgb(0x00, 0x2b, 0x36),
+            border: BorderType::Plain,
+        }
+    }
+
+    /// Built-in theme by name, with its colors mapped to ANSI ones unless the
+    /// terminal supports true color
+    pub fn preset(name: &str) -> Option<Self> {
+        let theme = match name {
+            "default" => Self::default(),
+            "retro" => Self::retro(),
+            "mono" => Self::mono(),
+            "dracula" => Self::dracula(),
+            "gruvbox" => Self::gruvbox(),
+            "solarized" => Self::solarized(),
+            _ => return None,
+        };
+        if supports_truecolor() {
+            return Some(theme);
+        }
+        Some(Self {
+            title: to_ansi(theme.title),
+            label: to_ansi(theme.label),
+            accent: to_ansi(theme.accent),
+            logo: to_ansi(theme.logo),
+            muted: to_ansi(theme.muted),
+            background: to_ansi(theme.background),
+            border: theme.border,
+        })
+    }
+
+    /// The preset after `name` in [`THEME_PRESETS`], wrapping around
+    pub fn next_preset(name: &str) -> &'static str {
+        let index = THEME_PRESETS.iter().position(|preset| *preset == name);
+        THEME_PRESETS[index.map_or(0, |index| (index + 1) % THEME_PRESETS.len())]
+    }
+
+    /// Build a theme from the config: start from the preset, then apply
+    /// the individual settings, keeping the preset value for unset or invalid ones.
+    /// Also describes each invalid value, like [`item_colors`].
+    pub fn from_config(config: &ThemeConfig) -> (Self, Vec<String>) {
+        let mut invalid = Vec::new();
+
+        let default = match config.preset.as_deref() {
+            Some(name) => Self::preset(name).unwrap_or_else(|| {
+                invalid.push(format!("unknown theme preset '{name}'"));
+                Self::default()
+            }),
+            None => Self::default(),
+        };
+        let border = match config.border.as_deref() {
+            Some(value) => parse_border(value).unwrap_or_else(|| {
+                invalid.push(format!("invalid border '{value}' for theme.border"));
+                default.border
+            }),
+            None => default.border,
+        };
+
+        let truecolor = supports_truecolor();
+        let mut pick = |role: &str, value: &Option<String>, default: Color| {
+            let Some(value) = value.as_deref() else {
+                return default;
+            };
+            match parse_color(value) {
+                Some(color) if truecolor => color,
+                Some(color) => to_ansi(color),
+                None => {
+                    invalid.push(format!("invalid color '{value}' for theme.{role}"));
+                    default
+                }
+            }
+        };
+        let theme = Self {
+            title: pick("title", &config.title, default.title),
+            label: pick("label", &config.label, default.label),
+            accent: pick("accent", &config.accent, default.accent),
+            logo: pick("logo", &config.logo, default.logo),
+            muted: pick("muted", &config.muted, default.muted),
+            background: pick("background", &config.background, default.background),
+            border,
+        };
+        (theme, invalid)
+    }
+}
+
+/// Parse the per-line color overrides, describing each value that is not a color
+pub fn item_colors(colors: &BTreeMap<InfoItem, String>) -> (HashMap<InfoItem, Color>, Vec<String>) {
+    let truecolor = supports_truecolor();
+    let mut parsed = HashMap::new();
+    let mut invalid = Vec::new();
+
+    for (item, value) in colors {
+        match parse_color(value) {
+            Some(color) => {
+                parsed.insert(*item, if truecolor { color } else { to_ansi(color) });
+            }
+            None => invalid.push(format!(
+                "invalid color '{value}' for colors.{}",
+                item.name()
+            )),
+        }
+    }
+
+    (parsed, invalid)
+}
+
+/// Parse a border style: `plain`, `rounded`, `double` or `thick`
+pub fn parse_border(value: &str) -> Option<BorderType> {
+    match value.trim() {
+        "plain" => Some(BorderType::Plain),
+        "rounded" => Some(BorderType::Rounded),
+        "double" => Some(BorderType::Double),
+        "thick" => Some(BorderType::Thick),
+        _ => None,
+    }
+}
+
+/// Parse a color name (`"cyan"`, `"light-red"`) or hex string (`"#8ec07c"`)
+pub fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if value.starts_with('#') {
+        parse_hex_color(value)
+    } else {
+        Color::from_str(value).ok()
+    }
+}
+
+/// Parse a `#rrggbb` or `#rgb` hex string into `Color::Rgb`
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).ok();
+    match hex.len() {
+        6 => Some(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?)),
+        3 => {
+            let (r, g, b) = (channel(0..1)?, channel(1..2)?, channel(2..3)?);
+            Some(Color::Rgb(r * 17, g * 17, b * 17))
+        }
+        _ => None,
+    }
+}
+
+/// Whether `NO_COLOR` is set to anything non-empty (<https://no-color.org>)
+pub fn no_color_env() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Drop every foreground and background color from a drawn frame, keeping bold,
+/// dim and the like so focus and alerts still stand out
+pub fn strip_colors(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
+    }
+}
+
+/// Whether the terminal advertises 24-bit color support
+pub fn supports_truecolor() -> bool {
+    matches!(
+        env::var("COLORTERM").as_deref(),
+        Ok("truecolor") | Ok("24bit")
+    )
+}
+
+/// Map an RGB color to the nearest of the 16 ANSI colors, leaving others untouched
+pub fn to_ansi(color: Color) -> Color {
+    // Reference values of the xterm default palette
+    const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    let distance = |(pr, pg, pb): (u8, u8, u8)| {
+        let dr = i32::from(r) - i32::from(pr);
+        let dg = i32::from(g) - i32::from(pg);
+        let db = i32::from(b) - i32::from(pb);
+        dr * dr + dg * dg + db * db
+    };
+
+    PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(ansi, _)| *ansi)
+        .unwrap_or(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_long_hex_colors() {
+        assert_eq!(parse_hex_color("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_hex_color("#00AAff"), Some(Color::Rgb(0, 170, 255)));
+    }
+
+    #[test]
+    fn expands_short_hex_colors() {
+        assert_eq!(parse_hex_color("#f80"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex_color("#000"), Some(Color::Rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_malformed_hex_colors() {
+        assert_eq!(parse_hex_color("#ggg"), None);
+        assert_eq!(parse_hex_color("#12345"), None);
+        assert_eq!(parse_hex_color("#+1ff00"), None);
+        assert_eq!(parse_hex_color("ff8000"), None);
+    }
+
+    #[test]
+    fn parse_color_accepts_hex_and_names() {
+        assert_eq!(parse_color(" #ff8000 "), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_color("cyan"), Some(Color::Cyan));
+        assert_eq!(parse_color("ff8000"), None);
+    }
+
+    #[test]
+    fn every_preset_parses_and_differs() {
+        // Compare the true color themes; the ANSI fallback may map two presets alike
+        env::set_var("COLORTERM", "truecolor");
+        let themes: Vec<Theme> = THEME_PRESETS
+            .iter()
+            .map(|name| Theme::preset(name).unwrap_or_else(|| panic!("preset {name}")))
+            .collect();
+        for (i, theme) in themes.iter().enumerate() {
+            for (other, name) in themes[i + 1..].iter().zip(&THEME_PRESETS[i + 1..]) {
+                assert_ne!(theme, other, "{} and {name}", THEME_PRESETS[i]);
+            }
+        }
+        assert!(Theme::preset("neon").is_none());
+    }
+
+    #[test]
+    fn next_preset_wraps_around() {
+        assert_eq!(Theme::next_preset(THEME_PRESETS[0]), THEME_PRESETS[1]);
+        let last = THEME_PRESETS[THEME_PRESETS.len() - 1];
+        assert_eq!(Theme::next_preset(last), THEME_PRESETS[0]);
+        assert_eq!(Theme::next_preset("neon"), THEME_PRESETS[0]);
+    }
+}
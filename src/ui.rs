@@ -1,224 +1,1911 @@
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Sparkline, Table, Wrap, block::Title,
+    },
 };
 
-use crate::app::App;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::app::{App, SLOW_REFRESH};
+use crate::config::{BarStyle, LayoutMode, LogoAlign};
+use crate::health::{self, Reading};
+use crate::history::{CoreHistory, History};
 use crate::logo;
+use crate::panel::{InfoItem, Panel};
+use crate::system_info::{
+    BatteryInfo, DiskInfo, GpuInfo, MemPressure, ProcessSort, format_bytes_with, format_temp,
+    format_uptime, format_uptime_with,
+};
+use crate::template;
+use crate::theme;
+
+/// Smallest area anything useful fits in
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 3;
+
+/// Temperature (°C) above which readings turn yellow, short of the red alert threshold
+const TEMP_WARM: f32 = 70.0;
+
+/// Battery percentage below which the gauge turns red
+const BATTERY_LOW: f32 = 20.0;
 
 pub fn draw(f: &mut Frame, app: &App) {
     let size = f.size();
 
+    // Redirected output and rapid resizes can report a degenerate size
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        let message = Paragraph::new("terminal too small").wrap(Wrap { trim: true });
+        f.render_widget(message, size);
+        return;
+    }
+
+    if app.theme.background != Color::Reset {
+        f.render_widget(
+            Block::default().style(Style::default().bg(app.theme.background)),
+            size,
+        );
+    }
+
+    if is_compact(app, size) {
+        draw_compact_layout(f, size, app);
+        draw_help_simple(f, size, app);
+    } else {
+        draw_full_layout(f, size, app);
+    }
+
+    if app.show_help {
+        draw_help_popup(f, size, app);
+    }
+
+    if app.no_color {
+        theme::strip_colors(f.buffer_mut());
+    }
+}
+
+fn draw_full_layout(f: &mut Frame, size: Rect, app: &App) {
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .margin(2)
         .constraints([
-            Constraint::Percentage(35), // left-side: ASCII art
-            Constraint::Percentage(65), // right-side: system information
+            Constraint::Percentage(app.logo_width), // left-side: ASCII art
+            Constraint::Percentage(100 - app.logo_width), // right-side: system information
         ])
         .split(size);
 
-    draw_ascii_art(f, main_chunks[0]);
-    draw_all_system_info(f, main_chunks[1], app);
+    draw_title(f, size, app);
+    draw_ascii_art(f, main_chunks[0], app);
+    draw_info(f, main_chunks[1], app);
 
-    draw_help_simple(f, size);
+    draw_help_simple(f, size, app);
 }
 
-fn draw_ascii_art(f: &mut Frame, area: ratatui::layout::Rect) {
-    let ascii_art = logo::get_logo();
-    let paragraph = Paragraph::new(ascii_art).alignment(Alignment::Left);
+/// Single bordered panel without the logo, for small terminals
+fn draw_compact_layout(f: &mut Frame, size: Rect, app: &App) {
+    let area = Rect {
+        height: size.height.saturating_sub(1),
+        ..size
+    };
+    let lines = summary_lines(app, area.width.saturating_sub(2));
+
+    let title = match title_text(app) {
+        Some(title) => format!(" {title} "),
+        None => " rsysfetch ".to_string(),
+    };
+    let block = bordered_block(Borders::ALL, app).title(title).title_style(
+        Style::default()
+            .fg(app.theme.title)
+            .add_modifier(Modifier::BOLD),
+    );
+    let (max_scroll, _) = scroll_range(app, size);
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: true })
+        .scroll((app.scroll_offset.min(max_scroll), 0));
     f.render_widget(paragraph, area);
 }
 
-fn draw_all_system_info(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+/// Whether everything goes into a single scrollable column
+fn is_compact(app: &App, size: Rect) -> bool {
+    match app.layout {
+        LayoutMode::Compact => true,
+        LayoutMode::Full => false,
+        LayoutMode::Auto => size.height < 20 || size.width < 60,
+    }
+}
+
+/// How far the compact layout can scroll, and the rows it shows at once; `(0, 0)` when nothing is clipped
+pub fn scroll_range(app: &App, size: Rect) -> (u16, u16) {
+    if !is_compact(app, size) || size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        return (0, 0);
+    }
+    // Less the help bar and the borders
+    let visible = size.height.saturating_sub(3);
+    let width = size.width.saturating_sub(2).max(1) as usize;
+    let rows: usize = summary_lines(app, width as u16)
+        .iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    let rows = u16::try_from(rows).unwrap_or(u16::MAX);
+    (rows.saturating_sub(visible), visible)
+}
+
+/// Every info line in one column, with usage bars sized to `width`
+pub fn summary_lines(app: &App, width: u16) -> Vec<Line<'_>> {
+    let mut lines = system_lines(app);
+    lines.extend(cpu_lines(app));
+    lines.push(cpu_bar(app, width));
+    lines.extend(gpu_lines(app));
+    lines.extend(vram_gpus(app).filter_map(|(number, gpu)| vram_bar(gpu, number, app, width)));
+    lines.push(memory_bar(app, width));
+    lines.push(swap_bar(app, width));
+    if let Some(battery) = &app.system_info.battery {
+        lines.push(battery_bar(battery, width, app));
+    }
+    lines.push(primary_disk_line(app, width));
+    lines.extend(network_lines(app));
+    lines
+}
+
+/// `Disk (<mount>) [███░░] x% used/total` for the configured primary mount
+fn primary_disk_line(app: &App, width: u16) -> Line<'_> {
+    let label = format!(" Disk ({})", app.primary_disk);
+    match app
+        .system_info
+        .disks
+        .iter()
+        .find(|disk| disk.mount_point == app.primary_disk)
+    {
+        Some(disk) => disk_bar(label, disk, width, app),
+        None => field_line(format!("{label}: "), optional(None::<&str>, app), app),
+    }
+}
+
+/// Label, bracketed block bar sized to fill `width`, then the percentage and amounts
+fn inline_bar<'a>(
+    label: String,
+    ratio: f64,
+    amounts: String,
+    width: u16,
+    item: Option<InfoItem>,
+    reading: Option<Reading>,
+    app: &App,
+) -> Line<'a> {
+    let alert = reading.and_then(|reading| alert_style(reading, app));
+    let item_color = item.and_then(|item| app.item_colors.get(&item));
+    let color = match (item, alert) {
+        (_, Some(_)) => Color::Red,
+        (Some(_), None) => item_color.copied().unwrap_or(app.theme.accent),
+        (None, None) => app.theme.muted,
+    };
+    let ratio = ratio.clamp(0.0, 1.0);
+    let numbers = format!(" {}% {amounts}", percent(ratio));
+
+    // Label, a space and the two brackets take width as well
+    let used = label.chars().count() + numbers.chars().count() + 3;
+    let bar_width = usize::from(width).saturating_sub(used).clamp(4, 40);
+    let filled = ((ratio * bar_width as f64).round() as usize).min(bar_width);
+
+    let line = Line::from(vec![
+        Span::styled(
+            label,
+            Style::default()
+                .fg(item_color.copied().unwrap_or(app.theme.label))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" ["),
+        Span::styled(
+            app.glyphs.bar_filled.repeat(filled),
+            Style::default().fg(color),
+        ),
+        Span::styled(
+            app.glyphs.bar_empty.repeat(bar_width - filled),
+            Style::default().fg(app.theme.muted),
+        ),
+        Span::raw("]"),
+        Span::raw(numbers),
+    ]);
+    with_alert(line, alert)
+}
+
+fn memory_bar(app: &App, width: u16) -> Line<'static> {
     let info = &app.system_info;
+    let ratio = usage_ratio(info.memory_used, info.memory_total);
+    inline_bar(
+        " Mem".to_string(),
+        ratio,
+        format!(
+            "{}/{}{}",
+            format_bytes_with(info.memory_used, app.byte_unit),
+            format_bytes_with(info.memory_total, app.byte_unit),
+            cgroup_note(info.cgroup.as_ref().and_then(|cgroup| cgroup.memory))
+        ),
+        width,
+        Some(InfoItem::Memory),
+        Some(Reading::Memory(health::percent(
+            info.memory_used,
+            info.memory_total,
+        ))),
+        app,
+    )
+}
+
+/// `VRAM n [███░░] x% used/total` for the `number`th GPU, when its driver reports VRAM
+fn vram_bar(gpu: &GpuInfo, number: usize, app: &App, width: u16) -> Option<Line<'static>> {
+    let (used, total) = gpu.vram_used.zip(gpu.vram_total)?;
+    Some(inline_bar(
+        format!(" VRAM {number}"),
+        usage_ratio(used, total),
+        format!(
+            "{}/{}",
+            format_bytes_with(used, app.byte_unit),
+            format_bytes_with(total, app.byte_unit)
+        ),
+        width,
+        Some(InfoItem::Gpu),
+        None,
+        app,
+    ))
+}
+
+/// GPUs with a VRAM reading, numbered like the GPU lines
+fn vram_gpus(app: &App) -> impl Iterator<Item = (usize, &GpuInfo)> {
+    app.system_info
+        .gpus
+        .iter()
+        .enumerate()
+        .filter(|(_, gpu)| gpu.vram_used.is_some() && gpu.vram_total.is_some())
+        .map(|(index, gpu)| (index + 1, gpu))
+}
+
+/// The swap usage bar, or a note when there is no swap at all
+fn swap_bar(app: &App, width: u16) -> Line<'static> {
+    let info = &app.system_info;
+    if info.swap_total == 0 {
+        return no_swap_line(app);
+    }
+    inline_bar(
+        " Swap".to_string(),
+        usage_ratio(info.swap_used, info.swap_total),
+        format!(
+            "{}/{}",
+            format_bytes_with(info.swap_used, app.byte_unit),
+            format_bytes_with(info.swap_total, app.byte_unit)
+        ),
+        width,
+        Some(InfoItem::Memory),
+        Some(Reading::Swap(health::percent(
+            info.swap_used,
+            info.swap_total,
+        ))),
+        app,
+    )
+}
+
+fn no_swap_line(app: &App) -> Line<'static> {
+    field_line(
+        " Swap: ",
+        Span::styled("No swap", Style::default().fg(app.theme.muted)),
+        app,
+    )
+}
+
+fn battery_bar(battery: &BatteryInfo, width: u16, app: &App) -> Line<'static> {
+    inline_bar(
+        " Battery".to_string(),
+        f64::from(battery.percent) / 100.0,
+        battery_note(battery),
+        width,
+        Some(InfoItem::Power),
+        None,
+        app,
+    )
+}
+
+/// State and time left, e.g. `Discharging, 2h 10m left`
+fn battery_note(battery: &BatteryInfo) -> String {
+    match (battery.state.as_str(), battery.time_remaining) {
+        ("Charging", Some(seconds)) => format!("Charging, {} to full", format_uptime(seconds)),
+        ("Discharging", Some(seconds)) => format!("Discharging, {} left", format_uptime(seconds)),
+        (state, _) => state.to_string(),
+    }
+}
+
+fn cpu_bar(app: &App, width: u16) -> Line<'static> {
+    inline_bar(
+        " Usage".to_string(),
+        f64::from(app.system_info.cpu_usage) / 100.0,
+        String::new(),
+        width,
+        Some(InfoItem::Cpu),
+        None,
+        app,
+    )
+}
+
+fn disk_bar(label: String, disk: &DiskInfo, width: u16, app: &App) -> Line<'static> {
+    let ratio = usage_ratio(disk.used, disk.total);
+    inline_bar(
+        label,
+        ratio,
+        format!(
+            "{}/{}",
+            format_bytes_with(disk.used, app.byte_unit),
+            format_bytes_with(disk.total, app.byte_unit)
+        ),
+        width,
+        Some(InfoItem::Disk),
+        Some(Reading::Disk(health::percent(disk.used, disk.total))),
+        app,
+    )
+}
+
+/// The configured title template resolved against the current info
+fn title_text(app: &App) -> Option<String> {
+    let template = app.title.as_deref()?;
+    Some(template::render(
+        template,
+        &app.system_info,
+        app.title_unknown,
+        &app.primary_disk,
+    ))
+}
+
+/// Centered title on the top row, inside the outer margin
+fn draw_title(f: &mut Frame, size: Rect, app: &App) {
+    let Some(title) = title_text(app) else {
+        return;
+    };
+
+    let area = Rect { height: 1, ..size };
+    let paragraph = Paragraph::new(Span::styled(
+        title,
+        Style::default()
+            .fg(app.theme.title)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .alignment(Alignment::Center);
+    f.render_widget(paragraph, area);
+}
+
+fn draw_ascii_art(f: &mut Frame, area: Rect, app: &App) {
+    let ascii_art = match &app.logo_image {
+        Some(image) => logo::braille_logo(
+            image,
+            area.width,
+            area.height,
+            app.logo_image_color,
+            &app.theme,
+        ),
+        // Oversized art is cut to the panel instead of wrapping
+        None => match &app.logo_text {
+            Some(lines) => logo::fit(lines, area.width, area.height),
+            None => logo::get_logo_for(app.system_info.os_name.as_deref(), &app.theme, app.glyphs),
+        },
+    };
+    let alignment = match app.logo_align {
+        LogoAlign::Left => Alignment::Left,
+        LogoAlign::Center => Alignment::Center,
+    };
+
+    // The palette goes one row below the logo, when there is room for it
+    let logo_height = ascii_art.len() as u16;
+    let palette_area = Rect {
+        y: area.y + logo_height + 1,
+        height: PALETTE_ROWS,
+        ..area
+    };
+    let paragraph = Paragraph::new(ascii_art).alignment(alignment);
+    f.render_widget(paragraph, area);
+
+    if palette_area.bottom() <= area.bottom() {
+        draw_color_palette(f, palette_area, alignment);
+    }
+}
+
+/// The 16 ANSI colors are shown as two rows of eight
+const PALETTE_ROWS: u16 = 2;
+
+/// Blocks in the terminal's own 16 colors, normal on the first row and bright on the second
+fn draw_color_palette(f: &mut Frame, area: Rect, alignment: Alignment) {
+    let lines: Vec<Line> = (0..PALETTE_ROWS as u8)
+        .map(|row| {
+            Line::from(
+                (0..8)
+                    .map(|column| {
+                        Span::styled("   ", Style::default().bg(Color::Indexed(row * 8 + column)))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).alignment(alignment), area);
+}
+
+/// Minimum width of one info column before the layout splits into more columns
+const COLUMN_WIDTH: u16 = 60;
+const MAX_COLUMNS: u16 = 3;
+
+fn column_count(width: u16) -> usize {
+    (width / COLUMN_WIDTH).clamp(1, MAX_COLUMNS) as usize
+}
+
+/// Split `items` into `columns` groups whose sizes differ by at most one, keeping order
+fn balance<T>(items: Vec<T>, columns: usize) -> Vec<Vec<T>> {
+    let base = items.len() / columns;
+    let extra = items.len() % columns;
+
+    let mut items = items.into_iter();
+    (0..columns)
+        .map(|column| {
+            let size = base + usize::from(column < extra);
+            items.by_ref().take(size).collect()
+        })
+        .collect()
+}
+
+fn column_areas(area: Rect, columns: usize) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+        .split(area)
+}
+
+/// System panel on top, hardware panels below; both spread over several columns when wide
+fn draw_info(f: &mut Frame, area: Rect, app: &App) {
+    let columns = column_count(area.width);
+
+    let system_height = if app.collapsed.contains(&Panel::System) {
+        1
+    } else {
+        system_lines(app).len().div_ceil(columns) as u16 + 2
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(system_height), Constraint::Min(0)])
+        .split(area);
+
+    draw_system_info(f, chunks[0], app, columns);
+    draw_hardware_info(f, chunks[1], app, columns);
+}
+
+fn draw_system_info(f: &mut Frame, area: Rect, app: &App, columns: usize) {
+    let block = panel_block(Panel::System, app);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.collapsed.contains(&Panel::System) {
+        return;
+    }
+
+    let groups = balance(system_lines(app), columns);
+    for (lines, column) in groups.into_iter().zip(column_areas(inner, columns).iter()) {
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+        f.render_widget(paragraph, *column);
+    }
+}
+
+fn draw_hardware_info(f: &mut Frame, area: Rect, app: &App, columns: usize) {
+    let panels: Vec<Panel> = app
+        .sections
+        .iter()
+        .copied()
+        .filter(|panel| app.panel_shown(*panel))
+        .collect();
+    let groups = balance(panels, columns);
+    for (panels, column) in groups.iter().zip(column_areas(area, columns).iter()) {
+        draw_panel_stack(f, *column, panels, app);
+    }
+}
+
+/// Stack panels vertically, giving collapsed ones a single title row
+fn draw_panel_stack(f: &mut Frame, area: Rect, panels: &[Panel], app: &App) {
+    let mut constraints: Vec<Constraint> = panels
+        .iter()
+        .map(|panel| Constraint::Length(panel_height(*panel, app)))
+        .collect();
+    constraints.push(Constraint::Min(0));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    for (panel, chunk) in panels.iter().zip(chunks.iter()) {
+        let block = panel_block(*panel, app);
+        if app.collapsed.contains(panel) {
+            f.render_widget(block, *chunk);
+            continue;
+        }
+
+        match panel {
+            Panel::Cpu => draw_cpu_info(f, *chunk, block, app),
+            Panel::Memory => draw_memory_info(f, *chunk, block, app),
+            Panel::Battery => draw_battery_info(f, *chunk, block, app),
+            Panel::Disk => draw_disk_info(f, *chunk, block, app),
+            Panel::Network => draw_network_info(f, *chunk, block, app),
+            Panel::Gpu => draw_gpu_info(f, *chunk, block, app),
+            Panel::Processes => draw_top_processes(f, *chunk, block, app),
+            _ => {
+                let paragraph = Paragraph::new(panel_lines(*panel, app))
+                    .block(block)
+                    .wrap(Wrap { trim: false });
+                f.render_widget(paragraph, *chunk);
+            }
+        }
+    }
+}
+
+fn panel_height(panel: Panel, app: &App) -> u16 {
+    if app.collapsed.contains(&panel) {
+        return 1;
+    }
+
+    let content = match panel {
+        // Text lines, the usage bar, its history and the heatmap
+        Panel::Cpu => panel_lines(panel, app).len() + 2 + heatmap_rows(app),
+        // Memory, its history, then swap
+        Panel::Memory => 3,
+        Panel::Battery => 1,
+        Panel::Disk => disk_rows(app).max(1),
+        // Text lines plus one sparkline row each for download and upload
+        Panel::Network => panel_lines(panel, app).len() + 2,
+        // Text lines plus a VRAM gauge for each GPU that reports it
+        Panel::Gpu => panel_lines(panel, app).len() + vram_gpus(app).count(),
+        // Header row, then one row per process
+        Panel::Processes => app.system_info.top_processes.len().max(1) + 1,
+        _ => panel_lines(panel, app).len(),
+    };
+    content as u16 + 2
+}
+
+fn panel_title(panel: Panel, app: &App) -> String {
+    let name = match panel {
+        Panel::System => "System",
+        Panel::Cpu => "CPU",
+        Panel::Memory => "Memory",
+        Panel::Battery => "Battery",
+        Panel::Disk => "Disk",
+        Panel::Gpu => "GPU",
+        Panel::Network => "Network",
+        Panel::Processes => "Processes",
+    };
+    format!(" {}{name} ", app.glyphs.panel_icon(panel))
+}
+
+/// A bordered block in the theme's border style, or ASCII borders when drawing with ASCII
+fn bordered_block<'a>(borders: Borders, app: &App) -> Block<'a> {
+    let block = Block::default()
+        .borders(borders)
+        .border_type(app.theme.border);
+    match app.glyphs.border {
+        Some(set) => block.border_set(set),
+        None => block,
+    }
+}
+
+fn panel_block(panel: Panel, app: &App) -> Block<'static> {
+    let theme = &app.theme;
+    let collapsed = app.collapsed.contains(&panel);
+
+    let border_style = if app.focused == panel {
+        Style::default().fg(theme.accent)
+    } else {
+        Style::default()
+    };
+
+    let borders = if collapsed {
+        Borders::TOP
+    } else {
+        Borders::ALL
+    };
+    bordered_block(borders, app)
+        .border_style(border_style)
+        .title(if collapsed {
+            format!("{}(+) ", panel_title(panel, app))
+        } else {
+            panel_title(panel, app)
+        })
+        .title_style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+}
+
+fn panel_lines(panel: Panel, app: &App) -> Vec<Line<'_>> {
+    match panel {
+        Panel::System => system_lines(app),
+        Panel::Cpu => cpu_lines(app),
+        Panel::Gpu => gpu_lines(app),
+        Panel::Network => network_lines(app),
+        Panel::Memory | Panel::Battery | Panel::Disk | Panel::Processes => Vec::new(),
+    }
+}
+
+fn field_line<'a>(
+    label: impl Into<Cow<'a, str>>,
+    value: impl Into<Span<'a>>,
+    app: &App,
+) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(
+            label,
+            Style::default()
+                .fg(app.theme.label)
+                .add_modifier(Modifier::BOLD),
+        ),
+        value.into(),
+    ])
+}
+
+/// Render a value that may be unknown, showing a dimmed dash in its place
+fn optional<'a, T: Into<Cow<'a, str>>>(value: Option<T>, app: &App) -> Span<'a> {
+    match value {
+        Some(value) => Span::raw(value),
+        None => Span::styled(
+            app.glyphs.unknown,
+            Style::default()
+                .fg(app.theme.muted)
+                .add_modifier(Modifier::DIM),
+        ),
+    }
+}
+
+fn system_lines(app: &App) -> Vec<Line<'_>> {
+    let info = &app.system_info;
+    let theme = &app.theme;
+
+    let os = info.os_name.as_ref().map(|name| match &info.os_version {
+        Some(version) => format!("{} {}, {}", name, version, info.os_arch),
+        None => format!("{}, {}", name, info.os_arch),
+    });
 
     let mut text = vec![
-        Line::from(vec![
-            Span::styled(
-                "  OS: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(format!(
-                "{} {}, {}",
-                info.os_name, info.os_version, info.os_arch
-            )),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  Kernel: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+        with_item_color(
+            InfoItem::Os,
+            field_line(" OS: ", optional(os, app), app),
+            app,
+        ),
+        with_item_color(
+            InfoItem::Kernel,
+            field_line(
+                " Kernel: ",
+                optional(info.kernel_version.as_deref(), app),
+                app,
             ),
-            Span::raw(&info.kernel_version),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  Host: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+            app,
+        ),
+    ];
+
+    if app.show_details {
+        if let Some(build) = &info.kernel_build {
+            let line = Line::from(vec![
+                Span::styled("   Build: ", Style::default().fg(theme.accent)),
+                Span::styled(build, Style::default().fg(theme.muted)),
+            ]);
+            text.push(with_item_color(InfoItem::Kernel, line, app));
+        }
+        if let Some(modules) = info.kernel_modules {
+            let line = Line::from(vec![
+                Span::styled("   Modules: ", Style::default().fg(theme.accent)),
+                Span::styled(modules.to_string(), Style::default().fg(theme.muted)),
+            ]);
+            text.push(with_item_color(InfoItem::Kernel, line, app));
+        }
+    }
+
+    let mut host = field_line(" Host: ", optional(info.hostname.as_deref(), app), app);
+    if info.is_virtual {
+        host.spans.push(Span::styled(
+            match &info.hypervisor {
+                Some(hypervisor) => format!(" (VM: {hypervisor})"),
+                None => " (VM)".to_string(),
+            },
+            Style::default().fg(app.theme.muted),
+        ));
+    }
+    text.push(with_item_color(InfoItem::Host, host, app));
+    // Left out rather than shown as unknown, since VMs rarely have one
+    if let Some(model) = &info.host_model {
+        text.push(with_item_color(
+            InfoItem::Host,
+            field_line(" Host Model: ", model.as_str(), app),
+            app,
+        ));
+    }
+    text.extend([with_item_color(
+        InfoItem::User,
+        field_line(" User: ", optional(info.username.as_deref(), app), app),
+        app,
+    )]);
+
+    if let Some(security) = &info.security {
+        let mut parts = Vec::new();
+        if let Some(secure_boot) = security.secure_boot {
+            parts.push(format!(
+                "SecureBoot: {}",
+                if secure_boot { "on" } else { "off" }
+            ));
+        }
+        if let Some(tpm) = &security.tpm {
+            parts.push(tpm.clone());
+        }
+        text.push(with_item_color(
+            InfoItem::Security,
+            field_line(" Security: ", parts.join(app.glyphs.separator), app),
+            app,
+        ));
+    }
+
+    text.extend([
+        with_item_color(
+            InfoItem::Uptime,
+            field_line(
+                " Uptime: ",
+                format_uptime_with(info.uptime_seconds, app.uptime_format),
+                app,
             ),
-            Span::raw(&info.hostname),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  User: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+            app,
+        ),
+        with_item_color(
+            InfoItem::Uptime,
+            field_line(" Booted: ", optional(info.boot_time.as_deref(), app), app),
+            app,
+        ),
+        with_item_color(
+            InfoItem::Uptime,
+            field_line(
+                " Record uptime: ",
+                format_uptime_with(app.uptime_record.seconds, app.uptime_format),
+                app,
             ),
-            Span::raw(&info.username),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  Uptime: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+            app,
+        ),
+    ]);
+
+    if let Some((one, five, fifteen)) = info.load_average {
+        let alert =
+            health::load_per_cpu(info).and_then(|load| alert_style(Reading::Load(load), app));
+        text.push(with_alert(
+            with_item_color(
+                InfoItem::Cpu,
+                field_line(" Load: ", format!("{one:.2} {five:.2} {fifteen:.2}"), app),
+                app,
             ),
-            Span::raw(&info.uptime),
-        ]),
-        Line::from(""),
-    ];
+            alert,
+        ));
+    }
 
-    for (i, cpu) in info.cpus.iter().enumerate() {
-        if i == 0 {
-            text.push(Line::from(Span::styled(
-                " 🔥 CPUs",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )));
-        }
-
-        let cpu_name = format!(
-            "{} ({} cores) @ {:.2}GHz",
-            cpu.model
-                .split_whitespace()
-                .take(4)
-                .collect::<Vec<_>>()
-                .join(" "),
-            cpu.cores,
-            cpu.frequency as f64 / 1000.0
-        );
+    if let Some(on_ac_power) = info.on_ac_power {
+        let mut power = if on_ac_power {
+            app.glyphs.ac_power.to_string()
+        } else {
+            app.glyphs.on_battery.to_string()
+        };
+        if let Some(lid_open) = info.lid_open {
+            power.push_str(if lid_open {
+                ", lid open"
+            } else {
+                ", lid closed"
+            });
+        }
+        text.push(with_item_color(
+            InfoItem::Power,
+            field_line(" Power: ", power, app),
+            app,
+        ));
+    }
+
+    if !info.packages.is_empty() {
+        let packages: Vec<String> = info
+            .packages
+            .iter()
+            .map(|(manager, count)| format!("{count} ({manager})"))
+            .collect();
+        text.push(with_item_color(
+            InfoItem::Packages,
+            field_line(" Packages: ", packages.join(", "), app),
+            app,
+        ));
+    }
+
+    text.extend([
+        with_item_color(
+            InfoItem::Shell,
+            field_line(" Shell: ", optional(info.shell.as_deref(), app), app),
+            app,
+        ),
+        with_item_color(
+            InfoItem::Terminal,
+            field_line(" Terminal: ", optional(info.terminal.as_deref(), app), app),
+            app,
+        ),
+    ]);
 
+    // Explains the unknown fields above; kept muted so it doesn't read as an alert
+    if !info.errors.is_empty() {
         text.push(Line::from(vec![
+            Span::styled(" Warnings: ", Style::default().fg(theme.muted)),
             Span::styled(
-                format!("  - CPU {}: ", i + 1),
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
+                info.errors.join("; "),
+                Style::default().fg(theme.muted).add_modifier(Modifier::DIM),
             ),
-            Span::raw(cpu_name),
         ]));
     }
 
-    for (i, gpu) in info.gpus.iter().enumerate() {
-        if i == 0 {
-            text.push(Line::from(Span::styled(
-                " 🎮 GPUs",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )));
-        }
+    text
+}
 
-        text.push(Line::from(vec![
+/// `3.60GHz`, or `800MHz` below a gigahertz
+fn format_frequency(mhz: u64) -> String {
+    if mhz >= 1000 {
+        format!("{:.2}GHz", mhz as f64 / 1000.0)
+    } else {
+        format!("{mhz}MHz")
+    }
+}
+
+fn cpu_lines(app: &App) -> Vec<Line<'_>> {
+    let theme = &app.theme;
+
+    let mut lines: Vec<Line> = app
+        .system_info
+        .cpus
+        .iter()
+        .enumerate()
+        .map(|(i, cpu)| {
+            // The vendor ID mostly repeats the model name, so it waits for the details view
+            let vendor = match &cpu.vendor {
+                Some(vendor) if app.show_details => format!("{vendor}, "),
+                _ => String::new(),
+            };
+            let mut cpu_name = format!(
+                "{} ({vendor}{}, {} cores)",
+                cpu.model
+                    .split_whitespace()
+                    .take(4)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                app.system_info.os_arch,
+                cpu.cores
+            );
+            // The live clock is only sampled for the first core
+            let current = app.system_info.cpu_frequency;
+            let frequency = if i == 0 && current > 0 {
+                current
+            } else {
+                cpu.frequency
+            };
+            // Some virtual CPUs report no clock at all
+            if frequency > 0 {
+                cpu_name.push_str(&format!(" @ {}", format_frequency(frequency)));
+            }
+
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" CPU {}: ", i + 1),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(cpu_name),
+            ]);
+            with_item_color(InfoItem::Cpu, line, app)
+        })
+        .collect();
+
+    if let Some(cpus) = app
+        .system_info
+        .cgroup
+        .as_ref()
+        .and_then(|cgroup| cgroup.cpus)
+    {
+        let line = field_line(" Limit: ", format!("{cpus:.1} CPUs (cgroup limit)"), app);
+        lines.push(with_item_color(InfoItem::Cpu, line, app));
+    }
+
+    let info = &app.system_info;
+    let governor = match (&info.cpu_governor, &info.power_profile) {
+        (Some(governor), Some(profile)) => Some(format!("{governor} ({profile} profile)")),
+        (Some(governor), None) => Some(governor.clone()),
+        (None, Some(profile)) => Some(format!("{profile} profile")),
+        (None, None) => None,
+    };
+    if let Some(governor) = governor {
+        let line = field_line(" Governor: ", governor, app);
+        lines.push(with_item_color(InfoItem::Cpu, line, app));
+    }
+
+    if let Some(temp) = app.system_info.cpu_temp {
+        // Thresholds are in Celsius regardless of the display unit
+        let line = field_line(" Temp: ", temp_span(temp, app), app);
+        lines.push(with_alert(
+            with_item_color(InfoItem::Cpu, line, app),
+            alert_style(Reading::CpuTemp(f64::from(temp)), app),
+        ));
+    }
+
+    lines
+}
+
+fn gpu_lines(app: &App) -> Vec<Line<'_>> {
+    let theme = &app.theme;
+
+    let mut lines = if app.system_info.gpus.is_empty() {
+        vec![with_item_color(
+            InfoItem::Gpu,
+            field_line(" GPU: ", optional(None::<&str>, app), app),
+            app,
+        )]
+    } else {
+        app.system_info
+            .gpus
+            .iter()
+            .enumerate()
+            .map(|(i, gpu)| {
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" GPU {}: ", i + 1),
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(match &gpu.driver_version {
+                        Some(version) => format!("{} ({})", gpu.name, version),
+                        None => gpu.name.clone(),
+                    }),
+                ];
+                let Some(temp) = gpu.temperature else {
+                    return with_item_color(InfoItem::Gpu, Line::from(spans), app);
+                };
+
+                spans.push(Span::raw(app.glyphs.separator));
+                spans.push(temp_span(temp, app));
+                with_alert(
+                    with_item_color(InfoItem::Gpu, Line::from(spans), app),
+                    alert_style(Reading::GpuTemp(f64::from(temp)), app),
+                )
+            })
+            .collect()
+    };
+
+    // The primary display first, then the rest in the order they were found
+    let mut displays: Vec<_> = app.system_info.displays.iter().collect();
+    displays.sort_by_key(|display| !display.primary);
+    if !displays.is_empty() {
+        let resolutions: Vec<String> = displays
+            .iter()
+            .map(|display| format!("{}x{}", display.width, display.height))
+            .collect();
+        let line = field_line(" Displays: ", resolutions.join(", "), app);
+        lines.push(with_item_color(InfoItem::Displays, line, app));
+    }
+
+    let line = field_line(
+        " Audio: ",
+        optional(app.system_info.audio_device.as_deref(), app),
+        app,
+    );
+    lines.push(with_item_color(InfoItem::Audio, line, app));
+
+    lines
+}
+
+fn network_lines(app: &App) -> Vec<Line<'_>> {
+    let local_ip = field_line(
+        " Local IP: ",
+        optional(app.system_info.local_ip.as_deref(), app),
+        app,
+    );
+    let mut lines = vec![with_item_color(InfoItem::Network, local_ip, app)];
+
+    if app.show_public_ip {
+        let value = match &app.system_info.public_ip {
+            Some(ip) => Span::raw(ip.as_str()),
+            None => Span::styled(
+                if app.public_ip_pending() {
+                    app.glyphs.pending
+                } else {
+                    "N/A"
+                },
+                Style::default().fg(app.theme.muted),
+            ),
+        };
+        let line = field_line(" Public IP: ", value, app);
+        lines.push(with_item_color(InfoItem::Network, line, app));
+    }
+
+    // One line per interface under the primary address; details add traffic, IPv6 and MAC
+    for interface in &app.system_info.interfaces {
+        let address = interface.ipv4.as_deref().or(interface.ipv6.as_deref());
+        let mut spans = vec![
             Span::styled(
-                format!("  - GPU {}: ", i + 1),
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
+                format!("   {}: ", interface.name),
+                Style::default().fg(app.theme.accent),
             ),
-            Span::raw(gpu.name.clone()),
-        ]));
+            optional(address, app),
+        ];
+        if app.show_details {
+            spans.push(Span::styled(
+                format!(
+                    "  {} {} {} {}",
+                    app.glyphs.download,
+                    format_bytes_with(interface.rx_total, app.byte_unit),
+                    app.glyphs.upload,
+                    format_bytes_with(interface.tx_total, app.byte_unit)
+                ),
+                Style::default().fg(app.theme.muted),
+            ));
+        }
+        lines.push(with_item_color(InfoItem::Network, Line::from(spans), app));
+
+        let extra: Vec<&str> = [
+            interface.ipv4.as_ref().and(interface.ipv6.as_deref()),
+            interface.mac.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if app.show_details && !extra.is_empty() {
+            let line = Line::from(Span::styled(
+                format!("     {}", extra.join(app.glyphs.separator)),
+                Style::default().fg(app.theme.muted),
+            ));
+            lines.push(with_item_color(InfoItem::Network, line, app));
+        }
     }
 
-    text.push(Line::from(""));
+    lines
+}
 
-    text.push(Line::from(vec![
-        Span::styled(
-            " 🌐 Local IP: ",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(&info.local_ip),
-    ]));
+/// Most rows the per-core heatmap takes; more cores are averaged into these
+const HEATMAP_MAX_ROWS: usize = 8;
+
+/// Rows of the per-core heatmap, shown with the details
+fn heatmap_rows(app: &App) -> usize {
+    if app.show_details {
+        app.core_history.cores().min(HEATMAP_MAX_ROWS)
+    } else {
+        0
+    }
+}
+
+fn draw_cpu_info(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // The usage bar goes right below the model lines
+    let mut models = cpu_lines(app);
+    let details = models.split_off(app.system_info.cpus.len().min(models.len()));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(models.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(details.len() as u16),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(models), rows[0]);
+    let usage = f64::from(app.system_info.cpu_usage);
+    if app.bar_style == BarStyle::Inline {
+        f.render_widget(Paragraph::new(cpu_bar(app, rows[1].width)), rows[1]);
+    } else {
+        let color = app
+            .item_colors
+            .get(&InfoItem::Cpu)
+            .copied()
+            .unwrap_or(app.theme.accent);
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(color))
+            .ratio((usage / 100.0).clamp(0.0, 1.0))
+            .label(format!("{usage:.0}%"));
+        render_gauge(f, gauge, rows[1], app);
+    }
+    draw_usage_history(f, rows[2], &app.cpu_history, app);
+    f.render_widget(Paragraph::new(details), rows[3]);
+    draw_heatmap(f, rows[4], &app.core_history, app);
+}
+
+/// Draw a gauge, swapping its block fill for the ASCII glyph when drawing with ASCII
+fn render_gauge(f: &mut Frame, gauge: Gauge, area: Rect, app: &App) {
+    f.render_widget(gauge, area);
+    let Some(fill) = app.glyphs.gauge_fill else {
+        return;
+    };
+    let buffer = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buffer.get_mut(x, y);
+            if cell.symbol() == symbols::block::FULL {
+                cell.set_symbol(fill);
+            }
+        }
+    }
+}
+
+/// Recent usage on a fixed 0-100% scale; the newest sample is on the right
+fn draw_usage_history(f: &mut Frame, area: Rect, history: &History, app: &App) {
+    let samples = history.latest(area.width as usize);
+    let sparkline = Sparkline::default()
+        .data(&samples)
+        .max(100)
+        .bar_set(app.glyphs.sparkline.clone())
+        .style(Style::default().fg(app.theme.accent));
+    f.render_widget(sparkline, area);
+}
+
+/// One row per core (or group of cores), one column per sample, colored by load
+fn draw_heatmap(f: &mut Frame, area: Rect, history: &CoreHistory, app: &App) {
+    const LABEL_WIDTH: usize = 8;
+
+    let cores = history.cores();
+    let rows = cores.min(usize::from(area.height));
+    if rows == 0 {
+        return;
+    }
+
+    let columns: Vec<&[f32]> = history
+        .latest(usize::from(area.width).saturating_sub(LABEL_WIDTH))
+        .collect();
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let range = row * cores / rows..(row + 1) * cores / rows;
+            let label = if range.len() == 1 {
+                format!(" {}", range.start)
+            } else {
+                format!(" {}-{}", range.start, range.end - 1)
+            };
+
+            let mut spans = vec![Span::styled(
+                format!("{label:LABEL_WIDTH$}"),
+                Style::default().fg(app.theme.label),
+            )];
+            spans.extend(columns.iter().map(|column| {
+                let group = &column[range.clone()];
+                let load = group.iter().sum::<f32>() / group.len() as f32;
+                Span::styled(app.glyphs.core_cell, Style::default().fg(load_color(load)))
+            }));
+            Line::from(spans)
+        })
+        .collect();
 
-    text.push(Line::from(vec![
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// Step through a cool-to-hot gradient by load percentage
+fn load_color(load: f32) -> Color {
+    match load {
+        load if load >= 90.0 => Color::Red,
+        load if load >= 70.0 => Color::LightRed,
+        load if load >= 50.0 => Color::Yellow,
+        load if load >= 25.0 => Color::Green,
+        load if load >= 5.0 => Color::Blue,
+        _ => Color::DarkGray,
+    }
+}
+
+fn draw_network_info(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines = network_lines(app);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(lines), rows[0]);
+
+    let interfaces = &app.system_info.interfaces;
+    let rx: u64 = interfaces.iter().map(|interface| interface.rx_rate).sum();
+    let tx: u64 = interfaces.iter().map(|interface| interface.tx_rate).sum();
+    let (down, up) = (app.glyphs.download, app.glyphs.upload);
+    draw_throughput(f, rows[1], &format!(" {down} "), rx, &app.rx_history, app);
+    draw_throughput(f, rows[2], &format!(" {up} "), tx, &app.tx_history, app);
+}
+
+/// One row with the current rate followed by a sparkline of recent rates
+fn draw_throughput(
+    f: &mut Frame,
+    area: Rect,
+    label: &str,
+    rate: u64,
+    history: &History,
+    app: &App,
+) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(16), Constraint::Min(0)])
+        .split(area);
+
+    let text = Line::from(vec![
         Span::styled(
-            " 🐚 Shell: ",
+            label,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.label)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw(&info.shell),
-    ]));
+        Span::raw(format!("{}/s", format_bytes_with(rate, app.byte_unit))),
+    ]);
+    f.render_widget(Paragraph::new(text), columns[0]);
 
-    text.push(Line::from(vec![
-        Span::styled(
-            " 📟 Terminal: ",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+    // Show as many samples as fit; the scale follows the largest visible value
+    let samples = history.latest(columns[1].width as usize);
+    let max = samples.iter().copied().max().unwrap_or(0).max(1);
+    let sparkline = Sparkline::default()
+        .data(&samples)
+        .max(max)
+        .bar_set(app.glyphs.sparkline.clone())
+        .style(Style::default().fg(app.theme.accent));
+    f.render_widget(sparkline, columns[1]);
+}
+
+fn draw_gpu_info(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // The VRAM gauges go right below the GPU lines, above displays and audio
+    let mut gpus = gpu_lines(app);
+    let details = gpus.split_off(app.system_info.gpus.len().max(1).min(gpus.len()));
+    let vram: Vec<(usize, &GpuInfo)> = vram_gpus(app).collect();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(gpus.len() as u16),
+            Constraint::Length(vram.len() as u16),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(gpus), rows[0]);
+    let gauge_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); vram.len()])
+        .split(rows[1]);
+    for ((number, gpu), row) in vram.into_iter().zip(gauge_rows.iter()) {
+        draw_vram_gauge(f, *row, gpu, number, app);
+    }
+    f.render_widget(Paragraph::new(details), rows[2]);
+}
+
+/// VRAM usage of one GPU, in the bar style of the memory gauge
+fn draw_vram_gauge(f: &mut Frame, area: Rect, gpu: &GpuInfo, number: usize, app: &App) {
+    let (Some(used), Some(total)) = (gpu.vram_used, gpu.vram_total) else {
+        return;
+    };
+    if app.bar_style == BarStyle::Inline {
+        let bar = vram_bar(gpu, number, app, area.width).unwrap_or_default();
+        f.render_widget(Paragraph::new(bar), area);
+        return;
+    }
+
+    let color = app
+        .item_colors
+        .get(&InfoItem::Gpu)
+        .copied()
+        .unwrap_or(app.theme.accent);
+    let ratio = usage_ratio(used, total);
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(format!(
+            "VRAM {number} {} / {} ({}%)",
+            format_bytes_with(used, app.byte_unit),
+            format_bytes_with(total, app.byte_unit),
+            percent(ratio)
+        ));
+    render_gauge(f, gauge, area, app);
+}
+
+fn draw_memory_info(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let info = &app.system_info;
+
+    let block = match info.memory_pressure {
+        Some(pressure) => {
+            let (text, color) = match pressure {
+                MemPressure::Normal => (" pressure: normal ", Color::Green),
+                MemPressure::Warning => (" pressure: warning ", Color::Yellow),
+                MemPressure::Critical => (" pressure: critical ", Color::Red),
+            };
+            block.title(
+                Title::from(Span::styled(
+                    text,
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            )
+        }
+        None => block,
+    };
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+    draw_usage_history(f, rows[1], &app.memory_history, app);
+    draw_swap_info(f, rows[2], app);
+
+    if app.bar_style == BarStyle::Inline {
+        f.render_widget(Paragraph::new(memory_bar(app, rows[0].width)), rows[0]);
+        return;
+    }
+
+    let ratio = usage_ratio(info.memory_used, info.memory_total);
+    let gauge = Gauge::default()
+        .gauge_style(gauge_style(
+            Reading::Memory(health::percent(info.memory_used, info.memory_total)),
+            app,
+        ))
+        .ratio(ratio)
+        .label(format!(
+            "{} / {} ({}%){}",
+            format_bytes_with(info.memory_used, app.byte_unit),
+            format_bytes_with(info.memory_total, app.byte_unit),
+            percent(ratio),
+            cgroup_note(info.cgroup.as_ref().and_then(|cgroup| cgroup.memory))
+        ));
+    render_gauge(f, gauge, rows[0], app);
+}
+
+/// Swap usage under the memory gauge; "No swap" rather than an empty bar
+fn draw_swap_info(f: &mut Frame, area: Rect, app: &App) {
+    let info = &app.system_info;
+    if info.swap_total == 0 || app.bar_style == BarStyle::Inline {
+        f.render_widget(Paragraph::new(swap_bar(app, area.width)), area);
+        return;
+    }
+
+    let ratio = usage_ratio(info.swap_used, info.swap_total);
+    let gauge = Gauge::default()
+        .gauge_style(gauge_style(
+            Reading::Swap(health::percent(info.swap_used, info.swap_total)),
+            app,
+        ))
+        .ratio(ratio)
+        .label(format!(
+            "Swap {} / {} ({}%)",
+            format_bytes_with(info.swap_used, app.byte_unit),
+            format_bytes_with(info.swap_total, app.byte_unit),
+            percent(ratio)
+        ));
+    render_gauge(f, gauge, area, app);
+}
+
+/// The processes using the most memory or CPU, with the sorted column marked
+fn draw_top_processes(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let processes = &app.system_info.top_processes;
+    let header_style = Style::default()
+        .fg(app.theme.label)
+        .add_modifier(Modifier::BOLD);
+    let sorted_style = Style::default()
+        .fg(app.theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let sorted = |name: &str| format!("{name}{}", app.glyphs.sorted);
+    let (cpu_style, memory_style, cpu_header, memory_header) = match app.process_sort {
+        ProcessSort::Cpu => (
+            sorted_style,
+            header_style,
+            sorted("CPU"),
+            "Memory".to_string(),
         ),
-        Span::raw(&info.terminal),
-    ]));
+        ProcessSort::Memory => (
+            header_style,
+            sorted_style,
+            "CPU".to_string(),
+            sorted("Memory"),
+        ),
+    };
+    let header = Row::new(vec![
+        Cell::from("PID").style(header_style),
+        Cell::from("Name").style(header_style),
+        Cell::from(cpu_header).style(cpu_style),
+        Cell::from(memory_header).style(memory_style),
+    ]);
+
+    let mut rows: Vec<Row> = processes
+        .iter()
+        .map(|process| {
+            Row::new(vec![
+                process.pid.to_string(),
+                process.name.clone(),
+                format!("{:.1}%", process.cpu),
+                format_bytes_with(process.memory, app.byte_unit),
+            ])
+        })
+        .collect();
+    if rows.is_empty() {
+        rows.push(Row::new(vec!["", "No processes"]).style(Style::default().fg(app.theme.muted)));
+    }
+
+    // Names take whatever the numbers and the column gaps leave
+    let name_width = block.inner(area).width.saturating_sub(8 + 8 + 12 + 3);
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(name_width.max(10)),
+        Constraint::Length(8),
+        Constraint::Length(12),
+    ];
+    let table = Table::new(rows, widths).header(header).block(block);
+    f.render_widget(table, area);
+}
+
+/// Charge gauge, green while charging and red when running low
+fn draw_battery_info(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let Some(battery) = &app.system_info.battery else {
+        return;
+    };
 
-    let memory_percent = if info.memory_total > 0 {
-        (info.memory_used as f64 / info.memory_total as f64 * 100.0) as u16
+    if app.bar_style == BarStyle::Inline {
+        let width = block.inner(area).width;
+        let line = battery_bar(battery, width, app);
+        f.render_widget(Paragraph::new(line).block(block), area);
+        return;
+    }
+
+    let color = if battery.state == "Charging" {
+        Color::Green
+    } else if battery.percent < BATTERY_LOW {
+        Color::Red
     } else {
-        0
+        app.item_colors
+            .get(&InfoItem::Power)
+            .copied()
+            .unwrap_or(app.theme.accent)
     };
-    text.push(Line::from(vec![
-        Span::styled(
-            " 💾 Memory: ",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(format!(
-            "{}MiB / {}MiB ({}%)",
-            info.memory_used / (1024 * 1024),
-            info.memory_total / (1024 * 1024),
-            memory_percent
-        )),
-    ]));
-
-    let paragraph = Paragraph::new(text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" 🖥️  Environments ")
-                .title_alignment(Alignment::Center)
-                .title_style(
+    let gauge = Gauge::default()
+        .block(block)
+        .gauge_style(Style::default().fg(color))
+        .ratio((f64::from(battery.percent) / 100.0).clamp(0.0, 1.0))
+        .label(format!(
+            "{:.0}%{}{}",
+            battery.percent,
+            app.glyphs.separator,
+            battery_note(battery)
+        ));
+    render_gauge(f, gauge, area, app);
+}
+
+/// One gauge row per disk, plus one per disk that reports inode counts
+fn disk_rows(app: &App) -> usize {
+    app.system_info
+        .disks
+        .iter()
+        .map(|disk| 1 + usize::from(disk.inodes_total.is_some()))
+        .sum()
+}
+
+fn draw_disk_info(f: &mut Frame, area: Rect, block: Block, app: &App) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let disks = &app.system_info.disks;
+    if disks.is_empty() {
+        let line = field_line(" Disk: ", optional(None::<&str>, app), app);
+        f.render_widget(Paragraph::new(line), inner);
+        return;
+    }
+
+    // Without room for a bar per row, list the disks as text instead
+    if usize::from(inner.height) < disk_rows(app) {
+        f.render_widget(
+            Paragraph::new(disk_text_lines(app, usize::from(inner.height))),
+            inner,
+        );
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); disk_rows(app)])
+        .split(inner);
+    let mut rows = rows.iter();
+
+    for disk in disks {
+        let Some(row) = rows.next() else {
+            break;
+        };
+        if app.bar_style == BarStyle::Inline {
+            let label = format!(" {} ({})", disk.mount_point, disk.fs_type);
+            f.render_widget(Paragraph::new(disk_bar(label, disk, row.width, app)), *row);
+        } else {
+            let ratio = usage_ratio(disk.used, disk.total);
+            let gauge = Gauge::default()
+                .gauge_style(gauge_style(
+                    Reading::Disk(health::percent(disk.used, disk.total)),
+                    app,
+                ))
+                .ratio(ratio)
+                .label(format!(
+                    "{} ({}) {} / {} ({}%)",
+                    disk.mount_point,
+                    disk.fs_type,
+                    format_bytes_with(disk.used, app.byte_unit),
+                    format_bytes_with(disk.total, app.byte_unit),
+                    percent(ratio)
+                ));
+            render_gauge(f, gauge, *row, app);
+        }
+
+        let (Some(total), Some(used)) = (disk.inodes_total, disk.inodes_used) else {
+            continue;
+        };
+        let Some(row) = rows.next() else {
+            break;
+        };
+        let ratio = usage_ratio(used, total);
+        if app.bar_style == BarStyle::Inline {
+            let bar = inline_bar(
+                "   inodes".to_string(),
+                ratio,
+                format!("{used}/{total}"),
+                row.width,
+                None,
+                None,
+                app,
+            );
+            f.render_widget(Paragraph::new(bar), *row);
+        } else {
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(app.theme.muted))
+                .ratio(ratio)
+                .label(format!("inodes {used} / {total} ({}%)", percent(ratio)));
+            render_gauge(f, gauge, *row, app);
+        }
+    }
+}
+
+/// Bold red on reverse video while the reading is beyond its threshold
+fn alert_style(reading: Reading, app: &App) -> Option<Style> {
+    app.thresholds.exceeded(reading).then(|| {
+        Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    })
+}
+
+/// Render the whole line in the alert style, if there is one
+fn with_alert(mut line: Line<'_>, alert: Option<Style>) -> Line<'_> {
+    if let Some(alert) = alert {
+        for span in &mut line.spans {
+            span.style = span.style.patch(alert);
+        }
+    }
+    line
+}
+
+/// One `mount: x% used/total` line per disk, ending in `+N more` when they do not fit
+fn disk_text_lines(app: &App, height: usize) -> Vec<Line<'static>> {
+    let disks = &app.system_info.disks;
+    // Keep a row for the `+N more` note, unless that would leave no disk at all
+    let shown = if disks.len() > height {
+        height.saturating_sub(1).max(1)
+    } else {
+        disks.len()
+    };
+
+    let mut lines: Vec<Line> = disks[..shown]
+        .iter()
+        .map(|disk| {
+            let usage = health::percent(disk.used, disk.total);
+            let line = field_line(
+                format!(" {}: ", disk.mount_point),
+                format!(
+                    "{usage:.0}% {}/{}",
+                    format_bytes_with(disk.used, app.byte_unit),
+                    format_bytes_with(disk.total, app.byte_unit)
+                ),
+                app,
+            );
+            with_alert(
+                with_item_color(InfoItem::Disk, line, app),
+                alert_style(Reading::Disk(usage), app),
+            )
+        })
+        .collect();
+    if shown < disks.len() && lines.len() < height {
+        lines.push(Line::styled(
+            format!(" +{} more", disks.len() - shown),
+            Style::default().fg(app.theme.muted),
+        ));
+    }
+    lines
+}
+
+/// Accent (or per-line) gauge, or the alert style without reverse video, which would hide
+/// the fill
+fn gauge_style(reading: Reading, app: &App) -> Style {
+    match alert_style(reading, app) {
+        Some(alert) => alert.remove_modifier(Modifier::REVERSED),
+        None => Style::default().fg(app
+            .item_colors
+            .get(&reading_item(reading))
+            .copied()
+            .unwrap_or(app.theme.accent)),
+    }
+}
+
+/// The info line a reading is shown on
+fn reading_item(reading: Reading) -> InfoItem {
+    match reading {
+        Reading::Memory(_) => InfoItem::Memory,
+        Reading::Disk(_) => InfoItem::Disk,
+        Reading::Swap(_) => InfoItem::Memory,
+        Reading::CpuTemp(_) | Reading::Load(_) => InfoItem::Cpu,
+        Reading::GpuTemp(_) => InfoItem::Gpu,
+    }
+}
+
+/// A temperature in the display unit, yellow once it runs warm
+fn temp_span(celsius: f32, app: &App) -> Span<'static> {
+    let color = if celsius > TEMP_WARM {
+        Color::Yellow
+    } else {
+        app.theme.accent
+    };
+    Span::styled(
+        format_temp(celsius, app.temp_unit),
+        Style::default().fg(color),
+    )
+}
+
+/// Render the whole line in the color configured for `item`, if there is one
+fn with_item_color<'a>(item: InfoItem, mut line: Line<'a>, app: &App) -> Line<'a> {
+    if let Some(color) = app.item_colors.get(&item) {
+        for span in &mut line.spans {
+            span.style = span.style.fg(*color);
+        }
+    }
+    line
+}
+
+/// Suffix marking a value capped by the container's cgroup
+fn cgroup_note<T>(limit: Option<T>) -> &'static str {
+    if limit.is_some() {
+        " (cgroup limit)"
+    } else {
+        ""
+    }
+}
+
+/// `used / total` within `0.0..=1.0`, which `Gauge::ratio` requires
+fn usage_ratio(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        (used as f64 / total as f64).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Whole percentage of a ratio, 0 for anything that is not a finite number
+fn percent(ratio: f64) -> u16 {
+    if ratio.is_finite() {
+        (ratio.clamp(0.0, 1.0) * 100.0) as u16
+    } else {
+        0
+    }
+}
+
+/// Key and what it does, in the order the help popup lists them
+const KEY_BINDINGS: [(&str, &str); 16] = [
+    ("q / Esc", "quit"),
+    ("r", "refresh now"),
+    ("f", "pause auto-refresh"),
+    ("+ / -", "refresh less / more often"),
+    ("Tab / Shift+Tab", "focus next / previous panel"),
+    ("Space", "collapse the focused panel"),
+    ("1-7", "hide a hardware panel"),
+    ("d", "toggle details"),
+    ("t", "next theme"),
+    ("p", "sort processes by CPU or memory"),
+    ("u", "switch between KiB and KB"),
+    ("s", "save a text report"),
+    ("Up / Down", "scroll the compact layout"),
+    ("PgUp / PgDn", "scroll by a page"),
+    ("?", "toggle this help"),
+    ("Esc", "close this help"),
+];
+
+/// Every key binding in a box centered over the rest of the UI
+fn draw_help_popup(f: &mut Frame, size: Rect, app: &App) {
+    let key_width = KEY_BINDINGS
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<Line> = KEY_BINDINGS
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(
+                    format!(" {key:<key_width$}  "),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(app.theme.label)
                         .add_modifier(Modifier::BOLD),
                 ),
-        )
-        .wrap(Wrap { trim: false });
-    f.render_widget(paragraph, area);
+                Span::raw(*action),
+            ])
+        })
+        .collect();
+
+    let width = lines
+        .iter()
+        .map(Line::width)
+        .max()
+        .unwrap_or(0)
+        .saturating_add(3) as u16;
+    let height = lines.len() as u16 + 2;
+    let area = Rect {
+        x: size.x + size.width.saturating_sub(width) / 2,
+        y: size.y + size.height.saturating_sub(height) / 2,
+        width: width.min(size.width),
+        height: height.min(size.height),
+    };
+
+    let block = bordered_block(Borders::ALL, app)
+        .title(" Keys ")
+        .title_style(
+            Style::default()
+                .fg(app.theme.title)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// `250ms` below a second, `1.25s` from there on
+fn format_interval(interval: Duration) -> String {
+    if interval < Duration::from_secs(1) {
+        format!("{}ms", interval.as_millis())
+    } else {
+        let seconds = format!("{:.2}", interval.as_secs_f64());
+        format!("{}s", seconds.trim_end_matches('0').trim_end_matches('.'))
+    }
 }
 
-fn draw_help_simple(f: &mut Frame, size: ratatui::layout::Rect) {
-    let help_area = ratatui::layout::Rect {
-        x: 0,
-        y: size.height.saturating_sub(1),
+fn draw_help_simple(f: &mut Frame, size: Rect, app: &App) {
+    let help_area = Rect {
+        x: size.x,
+        y: size.bottom().saturating_sub(1),
         width: size.width,
         height: 1,
     };
 
-    let help_text = Paragraph::new("Press 'q' or 'Esc' to quit")
-        .style(Style::default().fg(Color::Gray))
+    let glyphs = app.glyphs;
+    let sep = glyphs.separator;
+    let mut help = match &app.status {
+        Some(status) => status.clone(),
+        None => format!(
+            "'?': keys, Tab: focus, Space: collapse, 1-7: hide, {}: scroll, 'd': details, 'r': refresh, 'f': pause, 't': theme, 'q'/'Esc': quit",
+            glyphs.scroll_keys
+        ),
+    };
+
+    if app.refresh_count > 0 {
+        let mut refreshed = format!(
+            "#{}{sep}updated {:.1}s ago",
+            app.refresh_count,
+            app.last_refresh.elapsed().as_secs_f64()
+        );
+        if app.last_refresh_took > SLOW_REFRESH {
+            refreshed.push_str(&format!(
+                " (slow: {:.1}s)",
+                app.last_refresh_took.as_secs_f64()
+            ));
+        }
+        help = format!("{refreshed}{sep}{help}");
+    }
+    if app.refreshed_manually {
+        help = format!("Refreshed{sep}{help}");
+    }
+
+    if app.refresh_on_keypress_only {
+        help = format!("frozen{sep}{help}");
+    } else if app.paused {
+        help = format!("{}{sep}{help}", glyphs.paused);
+    } else {
+        help = format!("every {}{sep}{help}", format_interval(app.interval));
+    }
+
+    let unknown = app.system_info.unknown_count();
+    if unknown > 0 {
+        help = format!("{unknown} unknown{sep}{help}");
+    }
+
+    let help_text = Paragraph::new(help)
+        .style(Style::default().fg(app.theme.muted))
         .alignment(Alignment::Center);
     f.render_widget(help_text, help_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::system_info::SystemInfo;
+    use std::path::Path;
+
+    fn demo_app(config: &Config) -> App {
+        let info = SystemInfo::load_mock(Path::new("fixtures/demo.json")).unwrap();
+        App::with_info(config, info).unwrap()
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn primary_disk_line_shows_the_configured_mount() {
+        let config = Config {
+            primary_disk: Some("/".to_string()),
+            ascii: Some(true),
+            ..Config::default()
+        };
+        let app = demo_app(&config);
+        let text = line_text(&primary_disk_line(&app, 60));
+        assert!(text.starts_with(" Disk (/) ["), "{text}");
+        assert!(text.ends_with("] 41% 384.0 GiB/931.5 GiB"), "{text}");
+        assert_eq!(text.chars().count(), 60);
+    }
+
+    #[test]
+    fn primary_disk_line_marks_a_missing_mount_unknown() {
+        let config = Config {
+            primary_disk: Some("/srv".to_string()),
+            ascii: Some(true),
+            ..Config::default()
+        };
+        let app = demo_app(&config);
+        assert_eq!(line_text(&primary_disk_line(&app, 60)), " Disk (/srv): -");
+    }
+
+    fn render_system_info(app: &App) -> String {
+        let backend = ratatui::backend::TestBackend::new(80, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| draw_system_info(f, f.size(), app, 1))
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn load_average_line_follows_the_reading() {
+        let mut app = demo_app(&Config::default());
+        assert!(render_system_info(&app).contains("Load: 0.52 0.61 0.70"));
+
+        app.system_info.load_average = None;
+        assert!(!render_system_info(&app).contains("Load:"));
+    }
+}
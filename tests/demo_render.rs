@@ -0,0 +1,52 @@
+//! Renders the `--mock` fixture and compares the screen against a stored snapshot
+
+#![cfg(feature = "tui")]
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use rsysfetch::app::App;
+use rsysfetch::config::Config;
+use rsysfetch::system_info::SystemInfo;
+use rsysfetch::ui;
+use std::path::Path;
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 40;
+
+fn render_demo() -> String {
+    // The boot time is shown in local time
+    std::env::set_var("TZ", "UTC");
+    let info = SystemInfo::load_mock(Path::new("fixtures/demo.json")).unwrap();
+    // Pin the settings the environment would otherwise pick
+    let config = Config {
+        ascii: Some(false),
+        no_color: Some(true),
+        ..Config::default()
+    };
+    let app = App::with_info(&config, info).unwrap();
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|f| ui::draw(f, &app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let mut screen = String::new();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            screen.push_str(buffer.get(x, y).symbol());
+        }
+        screen.truncate(screen.trim_end().len());
+        screen.push('\n');
+    }
+    screen
+}
+
+#[test]
+fn demo_matches_snapshot() {
+    let screen = render_demo();
+    let snapshot = include_str!("snapshots/demo.txt");
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write("tests/snapshots/demo.txt", &screen).unwrap();
+        return;
+    }
+    assert_eq!(screen, snapshot, "screen:\n{screen}");
+}
@@ -0,0 +1,68 @@
+//! Runs the binary against the `--mock` fixture to check the threshold exit status
+
+#![cfg(feature = "tui")]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// Run `rsysfetch <args> --mock fixtures/demo.json` with `config` as its config file
+fn run_with_config(name: &str, config: &str, args: &[&str]) -> Output {
+    let dir = std::env::temp_dir().join(format!("rsysfetch-{name}-{}", std::process::id()));
+    let config_dir = dir.join("rsysfetch");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.toml"), config).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rsysfetch"))
+        .args(args)
+        .arg("--mock")
+        .arg(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/demo.json"))
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("APPDATA", &dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    output
+}
+
+#[test]
+fn breached_threshold_sets_the_exit_status() {
+    // The fixture uses 35% of its memory
+    let output = run_with_config(
+        "breached",
+        "[thresholds]\nmemory = 30\n",
+        &["--format", "json"],
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(rsysfetch::health::EXIT_THRESHOLD_BREACHED)
+    );
+    assert!(!output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("memory"));
+}
+
+#[test]
+fn thresholds_within_limits_exit_successfully() {
+    let output = run_with_config(
+        "within",
+        "[thresholds]\nmemory = 90\nswap = 50\n",
+        &["--format", "json"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn text_modes_report_breaches_too() {
+    let output = run_with_config("print", "[thresholds]\nmemory = 30\n", &["--print"]);
+    assert_eq!(
+        output.status.code(),
+        Some(rsysfetch::health::EXIT_THRESHOLD_BREACHED)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Arch Linux"));
+
+    let output = run_with_config("oneline", "[thresholds]\nmemory = 30\n", &["--oneline"]);
+    assert_eq!(
+        output.status.code(),
+        Some(rsysfetch::health::EXIT_THRESHOLD_BREACHED)
+    );
+}